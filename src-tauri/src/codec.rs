@@ -0,0 +1,124 @@
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine as _;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, NON_ALPHANUMERIC};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use strum_macros::Display;
+
+#[derive(Debug, Display, Serialize, Deserialize, Clone, Copy, Type, PartialEq)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum Codec {
+    Base64,
+    Base64Url,
+    Hex,
+    Url,
+}
+
+/// decoded payloads only count when they are readable text
+fn readable(bytes: Vec<u8>) -> Option<String> {
+    let text = String::from_utf8(bytes).ok()?;
+    if text.is_empty()
+        || text
+            .chars()
+            .any(|c| c.is_control() && !c.is_ascii_whitespace())
+    {
+        return None;
+    }
+    Some(text)
+}
+
+fn strip_hex_prefix(value: &str) -> &str {
+    value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+        .unwrap_or(value)
+}
+
+impl Codec {
+    pub fn decode(&self, value: &str) -> Result<String, String> {
+        let value = value.trim();
+        let bytes = match self {
+            Codec::Base64 => STANDARD.decode(value).map_err(|e| e.to_string())?,
+            Codec::Base64Url => URL_SAFE_NO_PAD
+                .decode(value.trim_end_matches('='))
+                .map_err(|e| e.to_string())?,
+            Codec::Hex => hex::decode(strip_hex_prefix(value)).map_err(|e| e.to_string())?,
+            Codec::Url => percent_decode_str(value).collect(),
+        };
+        readable(bytes).ok_or_else(|| format!("decoded {} is not readable text", self))
+    }
+
+    pub fn encode(&self, value: &str) -> String {
+        match self {
+            Codec::Base64 => STANDARD.encode(value),
+            Codec::Base64Url => URL_SAFE_NO_PAD.encode(value),
+            Codec::Hex => hex::encode(value),
+            Codec::Url => utf8_percent_encode(value, NON_ALPHANUMERIC).to_string(),
+        }
+    }
+
+    /// guesses the encoding of a clipboard value, plain words and hashes are ignored
+    /// because they do not decode to readable text
+    pub fn detect(value: &str) -> Option<Codec> {
+        let len = value.len();
+        if !(8..=1 << 20).contains(&len) || value.chars().any(char::is_whitespace) {
+            return None;
+        }
+        let is = |allowed: fn(char) -> bool| value.chars().all(allowed);
+        let hex = strip_hex_prefix(value);
+        // plain digit runs are ids and numbers far more often than hex
+        let looks_hex = hex.len() != len || hex.chars().any(|c| c.is_ascii_alphabetic());
+        let candidate = if !hex.is_empty()
+            && hex.chars().all(|c| c.is_ascii_hexdigit())
+            && hex.len().is_multiple_of(2)
+            && looks_hex
+        {
+            Codec::Hex
+        } else if is(|c| c.is_ascii_alphanumeric() || c == '+' || c == '/' || c == '=')
+            && len.is_multiple_of(4)
+        {
+            Codec::Base64
+        } else if is(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            Codec::Base64Url
+        } else if value.contains('%') {
+            Codec::Url
+        } else {
+            return None;
+        };
+        match candidate.decode(value) {
+            Ok(decoded) if decoded != value => Some(candidate),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(Codec::detect("aGVsbG8gd29ybGQ="), Some(Codec::Base64));
+        assert_eq!(Codec::detect("eyJhIjoxfQ"), Some(Codec::Base64Url));
+        assert_eq!(Codec::detect("68656c6c6f20776f726c64"), Some(Codec::Hex));
+        assert_eq!(Codec::detect("0x68656c6c6f"), Some(Codec::Hex));
+        assert_eq!(Codec::decode(&Codec::Hex, "0x68656c6c6f").unwrap(), "hello");
+        assert_eq!(Codec::detect("31323334"), None);
+        assert_eq!(Codec::detect("0x0102030405"), None);
+        assert_eq!(Codec::detect("hello%20world%21"), Some(Codec::Url));
+        assert_eq!(Codec::detect("password"), None);
+        assert_eq!(
+            Codec::detect("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for codec in [Codec::Base64, Codec::Base64Url, Codec::Hex, Codec::Url] {
+            let encoded = codec.encode("{\"a\": [1, 2]}");
+            assert_eq!(codec.decode(&encoded).unwrap(), "{\"a\": [1, 2]}");
+        }
+    }
+}
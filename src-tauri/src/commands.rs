@@ -1,11 +1,32 @@
+use crate::codec::Codec;
+use crate::convert;
 use crate::data::{Data, ProgrammingLanguage};
-use crate::{ClipboardHistory, PoolClipboard};
+use crate::detector::{self, DetectorInfo};
+use crate::diff::{self, Diff, DiffMode};
+use crate::extract::{self, ExtractKind, Extracted};
+use crate::formatter::{self, FormatError, FormatOptions};
+use crate::json;
+use crate::jwt::{self, DecodedJwt};
+use crate::lru_multi_thread::{now, Lru};
+use crate::query::{self, Query};
+use crate::ranking::Bounds;
+use crate::regex_search::{self, RegexError, RegexSearch};
+use crate::search_index::SearchMode;
+use crate::settings::{self, Settings};
+use crate::snippets::{Snippet, SnippetStore};
+use crate::template;
+use crate::transform::{self, Transform};
+use crate::{ClipboardHistory, PoolClipboard, SearchState};
+use arboard::Clipboard;
 use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher as _;
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
 use std::sync::{Arc, RwLock};
 use std::vec;
+use tauri::ipc::Channel;
 
 #[tauri::command]
 #[specta::specta]
@@ -27,7 +48,7 @@ pub fn get_all_id(
 #[specta::specta]
 pub fn get_by_id(id: &str, state: tauri::State<Arc<ClipboardHistory>>) -> Option<Data> {
     let map = state.data.read().unwrap();
-    map.get(id)
+    map.get(id).map(Data::masked)
 }
 
 #[tauri::command]
@@ -47,48 +68,176 @@ pub struct AllData {
 pub fn get_all_data(
     offset: u64,
     limit: u64,
+    tag: Option<String>,
     state: tauri::State<Arc<ClipboardHistory>>,
 ) -> Vec<AllData> {
     let map = state.data.read().unwrap();
     map.list()
         .iter()
+        .filter(|data| match &tag {
+            Some(tag) => map
+                .meta(&data.hash())
+                .is_some_and(|meta| meta.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))),
+            None => true,
+        })
         .skip(offset as usize)
         .take(limit as usize)
         .map(|data| AllData {
             id: data.hash(),
-            data,
+            data: data.masked(),
         })
         .collect()
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn tag_entry(
+    id: &str,
+    tag: &str,
+    state: tauri::State<Arc<ClipboardHistory>>,
+) -> Result<(), ()> {
+    let mut lru = state.data.write().unwrap();
+    lru.tag(id, tag).ok_or(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn untag_entry(
+    id: &str,
+    tag: &str,
+    state: tauri::State<Arc<ClipboardHistory>>,
+) -> Result<(), ()> {
+    let mut lru = state.data.write().unwrap();
+    lru.untag(id, tag).ok_or(())
+}
+
+/// a tag and how many entries carry it
+#[derive(Deserialize, Serialize, Type)]
+pub struct Collection {
+    pub name: String,
+    pub count: u32,
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn list_collections(state: tauri::State<Arc<ClipboardHistory>>) -> Vec<Collection> {
+    let lru = state.data.read().unwrap();
+    lru.collections()
+        .into_iter()
+        .map(|(name, count)| Collection { name, count })
+        .collect()
+}
+
 // ----------------------- FUZZY SEARCH --------------------- //
+#[derive(Deserialize, Serialize, Type, Clone)]
+pub struct SearchResult {
+    pub id: String,
+    /// blended rank results are ordered by, see `Ranking`
+    pub rank: f64,
+    /// raw fuzzy match score
+    pub score: i64,
+    /// char positions of the matched query characters, for highlighting
+    pub indices: Vec<u32>,
+    pub data: Data,
+}
+
+type Ranked = (f64, i64, String, Data);
+
+/// scores every entry against `query`, best first by the configured ranking. returns `None`
+/// once `cancelled` reports true
+fn rank(
+    lru: &Lru,
+    query: &Query,
+    matcher: &SkimMatcherV2,
+    cancelled: impl Fn() -> bool,
+) -> Option<Vec<Ranked>> {
+    let text = query.text.to_lowercase();
+    // match against the index text so only the hits are cloned
+    let matches: Vec<_> = lru
+        .index()
+        .texts()
+        .into_iter()
+        .take_while(|_| !cancelled())
+        .filter(|(_, entry)| query.matches_text(entry))
+        .filter_map(|(id, entry)| match text.is_empty() {
+            true => Some((0, id)),
+            false => matcher.fuzzy_match(entry, &text).map(|score| (score, id)),
+        })
+        .filter_map(|(score, id)| {
+            let data = lru.get(id)?;
+            let meta = lru.meta(id)?;
+            query
+                .matches_entry(&data, meta)
+                .then_some((score, id, meta, data))
+        })
+        .collect();
+    if cancelled() {
+        return None;
+    }
+    let ranking = settings::get().ranking;
+    let bounds = Bounds {
+        max_score: matches.iter().map(|m| m.0).max().unwrap_or_default(),
+        max_count: matches
+            .iter()
+            .map(|m| m.2.copy_count)
+            .max()
+            .unwrap_or_default(),
+    };
+    let now = now();
+    let mut sorted: Vec<Ranked> = matches
+        .into_iter()
+        .map(|(score, id, meta, data)| {
+            let rank = ranking.rank(score, meta, bounds, now);
+            (rank, score, id.to_string(), data)
+        })
+        .collect();
+    sorted.sort_by(|a, b| b.0.total_cmp(&a.0));
+    Some(sorted)
+}
+
+fn search_result(
+    matcher: &SkimMatcherV2,
+    query: &Query,
+    (rank, score, id, data): Ranked,
+) -> SearchResult {
+    let data = data.masked();
+    // the lowercase query matches case insensitively, so the indices line up with the
+    // original text
+    let indices = matcher
+        .fuzzy_indices(&data.val(), &query.text.to_lowercase())
+        .map(|(_, indices)| indices.into_iter().map(|i| i as u32).collect())
+        .unwrap_or_default();
+    SearchResult {
+        id,
+        rank,
+        score,
+        indices,
+        data,
+    }
+}
+
+/// `query` supports filters, see `query::parse`, the remaining words are fuzzy matched
 #[tauri::command]
 #[specta::specta]
 pub async fn fuzzy_search(
     query: String,
+    offset: u64,
+    limit: u64,
     state: tauri::State<'_, Arc<ClipboardHistory>>,
-) -> Result<Vec<Data>, String> {
+) -> Result<Vec<SearchResult>, String> {
+    let query = query::parse(&query)?;
     let arc_state = state.inner().clone();
     let new_handle = std::thread::spawn(move || {
         // use std thread
         let matcher = SkimMatcherV2::default();
         let lru = arc_state.data.read().expect("lru unlocked".into());
-        // Perform fuzzy search on the data stored in your hashmap
-        let results: Vec<_> = lru
-            .list()
-            .iter()
-            .filter_map(|data| {
-                matcher
-                    .fuzzy_match(&data.val(), &query)
-                    .map(|score| (score, data))
-            })
-            .collect();
-        let mut sorted = results;
-        sorted.sort_by(|a, b| b.0.cmp(&a.0));
-        sorted
+        rank(&lru, &query, &matcher, || false)
+            .unwrap_or_default()
             .into_iter()
-            .map(|(_, val)| val)
-            .collect::<Vec<Data>>()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .map(|ranked| search_result(&matcher, &query, ranked))
+            .collect::<Vec<SearchResult>>()
     });
     match new_handle.join() {
         Ok(vec) => Ok(vec),
@@ -96,50 +245,754 @@ pub async fn fuzzy_search(
     }
 }
 
+#[derive(Deserialize, Serialize, Type, Clone)]
+#[serde(tag = "tag", content = "content")]
+pub enum SearchEvent {
+    /// next best results, in rank order
+    Batch {
+        generation: u64,
+        results: Vec<SearchResult>,
+    },
+    Done {
+        generation: u64,
+        total: u64,
+    },
+}
+
+/// ranks and sends the results of search `generation` in batches. stops without `Done` once
+/// a newer search bumps the generation or `send` reports the receiver is gone
+fn stream(
+    history: &ClipboardHistory,
+    search: &SearchState,
+    generation: u64,
+    query: &Query,
+    batch_size: u64,
+    mut send: impl FnMut(SearchEvent) -> bool,
+) {
+    let superseded = || search.generation.load(Ordering::SeqCst) != generation;
+    let matcher = SkimMatcherV2::default();
+    let lru = history.data.read().unwrap();
+    let Some(ranked) = rank(&lru, query, &matcher, superseded) else {
+        return;
+    };
+    drop(lru);
+    let total = ranked.len() as u64;
+    let mut ranked = ranked.into_iter().peekable();
+    while ranked.peek().is_some() {
+        if superseded() {
+            return;
+        }
+        let results = ranked
+            .by_ref()
+            .take(batch_size.max(1) as usize)
+            .map(|ranked| search_result(&matcher, query, ranked))
+            .collect();
+        if !send(SearchEvent::Batch {
+            generation,
+            results,
+        }) {
+            return;
+        }
+    }
+    send(SearchEvent::Done { generation, total });
+}
+
+/// like `fuzzy_search` but returns straight away with the search generation and sends the
+/// ranked results over `on_event` in batches. starting a new search cancels the previous one,
+/// which then stops without sending `Done`
+#[tauri::command]
+#[specta::specta]
+pub fn stream_search(
+    query: String,
+    batch_size: u64,
+    on_event: Channel<SearchEvent>,
+    state: tauri::State<Arc<ClipboardHistory>>,
+    search: tauri::State<Arc<SearchState>>,
+) -> Result<u64, String> {
+    let query = query::parse(&query)?;
+    let generation = search.generation.fetch_add(1, Ordering::SeqCst) + 1;
+    let history = state.inner().clone();
+    let search = search.inner().clone();
+    std::thread::spawn(move || {
+        stream(&history, &search, generation, &query, batch_size, |event| {
+            on_event.send(event).is_ok()
+        })
+    });
+    Ok(generation)
+}
+
+/// cancels the running streaming search, if any
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_search(search: tauri::State<Arc<SearchState>>) {
+    search.generation.fetch_add(1, Ordering::SeqCst);
+}
+
+#[derive(Deserialize, Serialize, Type)]
+pub struct SimilarResult {
+    pub id: String,
+    /// cosine similarity, 0..1
+    pub similarity: f64,
+    pub data: Data,
+}
+
+/// entries about the same thing as `query` even when the words differ slightly, computed from
+/// local hashed n-gram vectors
+#[tauri::command]
+#[specta::specta]
+pub fn similar_search(
+    query: &str,
+    limit: u64,
+    state: tauri::State<Arc<ClipboardHistory>>,
+) -> Result<Vec<SimilarResult>, String> {
+    let lru = state.data.read().unwrap();
+    let similar = lru
+        .index()
+        .similar(query, limit as usize)
+        .ok_or_else(|| "similarity search is turned off in settings".to_string())?;
+    let results = similar
+        .into_iter()
+        .filter_map(|(id, similarity)| {
+            lru.get(&id).map(|data| SimilarResult {
+                id,
+                similarity,
+                data: data.masked(),
+            })
+        })
+        .collect();
+    Ok(results)
+}
+
+/// entries matching `pattern`, most recent first. stops early with `timed_out` set when the
+/// scan runs past its deadline
+#[tauri::command]
+#[specta::specta]
+pub async fn regex_search(
+    pattern: String,
+    case_insensitive: bool,
+    offset: u64,
+    limit: u64,
+    state: tauri::State<'_, Arc<ClipboardHistory>>,
+) -> Result<RegexSearch, RegexError> {
+    let regex = regex_search::compile(&pattern, case_insensitive)?;
+    let lru = state.data.read().unwrap();
+    Ok(regex_search::search(
+        &regex,
+        lru.list().iter(),
+        offset as usize,
+        limit as usize,
+    ))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_pinned(
+    id: &str,
+    pinned: bool,
+    state: tauri::State<Arc<ClipboardHistory>>,
+) -> Result<(), ()> {
+    let mut lru = state.data.write().unwrap();
+    lru.meta_mut(id).ok_or(())?.pinned = pinned;
+    Ok(())
+}
+
+/// substring or whole word search answered from the search index, most recent first
+#[tauri::command]
+#[specta::specta]
+pub fn search(
+    query: &str,
+    mode: SearchMode,
+    offset: u64,
+    limit: u64,
+    state: tauri::State<Arc<ClipboardHistory>>,
+) -> Vec<AllData> {
+    let lru = state.data.read().unwrap();
+    lru.index()
+        .query(query, mode)
+        .into_iter()
+        .skip(offset as usize)
+        .take(limit as usize)
+        .filter_map(|id| {
+            lru.get(&id).map(|data| AllData {
+                id,
+                data: data.masked(),
+            })
+        })
+        .collect()
+}
+
+/// returns the new id of the entry, ids follow the value
 #[tauri::command]
 #[specta::specta]
 pub async fn update_data_by_id(
     id: &str,
     new_data: Data,
     state: tauri::State<'_, Arc<ClipboardHistory>>,
-) -> Result<(), ()> {
-    let lru = state.data.write().unwrap();
-    if let Some(data) = lru.get_mutex(id) {
-        let mut data = data.write().unwrap();
-        (*data).set_value(new_data);
-        Ok(())
-    } else {
-        Err(())
-    }
+) -> Result<String, ()> {
+    let mut lru = state.data.write().unwrap();
+    lru.update(id, new_data).ok_or(())
+}
+
+/// previous values of an entry, oldest first
+#[tauri::command]
+#[specta::specta]
+pub fn list_revisions(id: &str, state: tauri::State<Arc<ClipboardHistory>>) -> Vec<Data> {
+    let lru = state.data.read().unwrap();
+    lru.get_mutex(id)
+        .map(|node| {
+            let node = node.read().unwrap();
+            node.revisions().iter().cloned().map(Data::masked).collect()
+        })
+        .unwrap_or_default()
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn revert_revision(
+    id: &str,
+    index: u64,
+    state: tauri::State<Arc<ClipboardHistory>>,
+) -> Result<String, ()> {
+    let mut lru = state.data.write().unwrap();
+    lru.revert(id, index as usize).ok_or(())
+}
+
+/// pretty prints a json entry, returns its new id
 #[tauri::command]
 #[specta::specta]
 pub async fn format_json(
     id: &str,
     state: tauri::State<'_, Arc<ClipboardHistory>>,
-) -> Result<(), ()> {
-    let lru = state.data.write().unwrap();
-    if let Some(data) = lru.get_mutex(id) {
-        let mut data_ = data.write().unwrap();
-        match data_.val() {
-            Data::Code {
-                data,
-                lang: ProgrammingLanguage::JSON,
-            } => {
-                data_.set_value(Data::Code {
-                    data: jsonformat::format(&data, jsonformat::Indentation::TwoSpace),
-                    lang: ProgrammingLanguage::JSON,
-                });
-                Ok(())
+) -> Result<String, ()> {
+    let mut lru = state.data.write().unwrap();
+    let formatted = match lru.get(id).ok_or(())? {
+        Data::Code {
+            data,
+            lang: ProgrammingLanguage::JSON,
+        } => Data::Code {
+            data: json::format(&data, "  ").ok_or(())?,
+            lang: ProgrammingLanguage::JSON,
+        },
+        Data::JsonDict { data, value } => Data::JsonDict {
+            data: json::format(&data, "  ").ok_or(())?,
+            value,
+        },
+        _ => return Err(()),
+    };
+    lru.update(id, formatted).ok_or(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn format_entry(
+    id: &str,
+    options: FormatOptions,
+    state: tauri::State<'_, Arc<ClipboardHistory>>,
+) -> Result<String, FormatError> {
+    let mut lru = state.data.write().unwrap();
+    let data = lru.get(id).ok_or(FormatError::NotFound)?;
+    let lang = options
+        .lang
+        .or(data.lang())
+        .ok_or(FormatError::Unsupported(ProgrammingLanguage::String))?;
+    let formatted = formatter::format(&data.val(), lang, &options)?;
+    let formatted = match data {
+        Data::JsonDict { data: _, value } if lang == ProgrammingLanguage::JSON => Data::JsonDict {
+            data: formatted,
+            value,
+        },
+        _ => Data::Code {
+            data: formatted,
+            lang,
+        },
+    };
+    lru.update(id, formatted).ok_or(FormatError::NotFound)
+}
+
+/// minifies the entry in place, or into a new entry when `new_entry` is set. returns the id of
+/// the minified entry
+#[tauri::command]
+#[specta::specta]
+pub async fn minify_entry(
+    id: &str,
+    lang: Option<ProgrammingLanguage>,
+    new_entry: bool,
+    state: tauri::State<'_, Arc<ClipboardHistory>>,
+) -> Result<String, FormatError> {
+    let mut lru = state.data.write().unwrap();
+    let data = lru.get(id).ok_or(FormatError::NotFound)?;
+    let lang = lang
+        .or(data.lang())
+        .ok_or(FormatError::Unsupported(ProgrammingLanguage::String))?;
+    let minified = match (formatter::minify(&data.val(), lang)?, data) {
+        (minified, Data::JsonDict { data: _, value }) if lang == ProgrammingLanguage::JSON => {
+            Data::JsonDict {
+                data: minified,
+                value,
             }
-            _ => Err(()),
         }
+        (minified, _) => Data::Code {
+            data: minified,
+            lang,
+        },
+    };
+    if new_entry {
+        Ok(lru.insert_data(minified))
     } else {
-        Err(())
+        lru.update(id, minified).ok_or(FormatError::NotFound)
     }
 }
 
+/// converts between json, yaml, toml and csv into a new entry, returns the new id
+#[tauri::command]
+#[specta::specta]
+pub async fn convert_entry(
+    id: &str,
+    target_lang: ProgrammingLanguage,
+    source_lang: Option<ProgrammingLanguage>,
+    state: tauri::State<'_, Arc<ClipboardHistory>>,
+) -> Result<String, FormatError> {
+    let mut lru = state.data.write().unwrap();
+    let data = lru.get(id).ok_or(FormatError::NotFound)?;
+    let value = match (source_lang, data) {
+        (None | Some(ProgrammingLanguage::JSON), Data::JsonDict { data: _, value }) => {
+            value.into_inner().clone()
+        }
+        (source_lang, data) => {
+            let lang = source_lang
+                .or(data.lang())
+                .ok_or(FormatError::Unsupported(ProgrammingLanguage::String))?;
+            convert::parse(&data.val(), lang)?
+        }
+    };
+    let converted = convert::serialize(&value, target_lang)?;
+    Ok(lru.insert_data(match target_lang {
+        ProgrammingLanguage::JSON => Data::JsonDict {
+            data: converted,
+            value: value.into(),
+        },
+        lang => Data::Code {
+            data: converted,
+            lang,
+        },
+    }))
+}
+
+/// applies the transforms in order, in place or into a new entry when `new_entry` is set.
+/// the result is classified again, returns the id of the transformed entry
+#[tauri::command]
+#[specta::specta]
+pub async fn transform_entry(
+    id: &str,
+    transforms: Vec<Transform>,
+    new_entry: bool,
+    state: tauri::State<'_, Arc<ClipboardHistory>>,
+) -> Result<String, String> {
+    let mut lru = state.data.write().unwrap();
+    let text = lru
+        .get(id)
+        .ok_or_else(|| "entry not found".to_string())?
+        .val();
+    let transformed = transform::apply_all(&text, &transforms)?;
+    if new_entry {
+        Ok(lru.insert(transformed))
+    } else {
+        lru.update(id, Data::from(transformed))
+            .ok_or_else(|| "entry not found".to_string())
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn diff_entries(
+    id_a: &str,
+    id_b: &str,
+    mode: DiffMode,
+    state: tauri::State<'_, Arc<ClipboardHistory>>,
+) -> Result<Diff, String> {
+    let lru = state.data.read().unwrap();
+    let a = lru.get(id_a).ok_or_else(|| "entry not found".to_string())?;
+    let b = lru.get(id_b).ok_or_else(|| "entry not found".to_string())?;
+    drop(lru);
+    match mode {
+        DiffMode::Json => {
+            let json = |data: &Data| match data {
+                Data::JsonDict { data: _, value } => Ok(value.into_inner().clone()),
+                data => json::parse(&data.val()).ok_or_else(|| "entry is not json".to_string()),
+            };
+            Ok(diff::diff_json(&json(&a)?, &json(&b)?))
+        }
+        mode => Ok(diff::diff_text(&a.val(), &b.val(), mode)),
+    }
+}
+
+#[derive(Deserialize, Serialize, Type, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum JoinMode {
+    /// joins the text of the entries with the separator
+    Concat,
+    /// deep merges json objects or concatenates json arrays, the separator is ignored
+    Merge,
+}
+
+/// joins the entries in the given order into a new entry, returns the new id
+#[tauri::command]
+#[specta::specta]
+pub async fn join_entries(
+    ids: Vec<String>,
+    separator: String,
+    mode: JoinMode,
+    state: tauri::State<'_, Arc<ClipboardHistory>>,
+) -> Result<String, String> {
+    if ids.is_empty() {
+        return Err("no entries to join".into());
+    }
+    let mut lru = state.data.write().unwrap();
+    let entries = ids
+        .iter()
+        .map(|id| lru.get(id).ok_or_else(|| format!("entry {} not found", id)))
+        .collect::<Result<Vec<_>, _>>()?;
+    match mode {
+        JoinMode::Concat => {
+            let joined = entries
+                .iter()
+                .map(Data::val)
+                .collect::<Vec<_>>()
+                .join(&separator);
+            Ok(lru.insert(joined))
+        }
+        JoinMode::Merge => {
+            let mut merged: Option<serde_json::Value> = None;
+            for entry in entries {
+                let value = match entry {
+                    Data::JsonDict { data: _, value } => value.into_inner().clone(),
+                    data => json::parse(&data.val())
+                        .ok_or_else(|| "only json entries can be merged".to_string())?,
+                };
+                match merged.as_mut() {
+                    Some(merged) if merged.is_object() != value.is_object() => {
+                        return Err("cannot merge json objects with arrays".into())
+                    }
+                    Some(merged) => json::merge(merged, value),
+                    None => merged = Some(value),
+                }
+            }
+            let merged = merged.ok_or_else(|| "no entries to merge".to_string())?;
+            Ok(lru.insert_data(Data::JsonDict {
+                data: serde_json::to_string_pretty(&merged).map_err(|e| e.to_string())?,
+                value: merged.into(),
+            }))
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Type, Clone)]
+#[serde(tag = "tag", content = "content")]
+pub enum Splitter {
+    Lines,
+    Delimiter(String),
+    Regex(String),
+}
+
+/// splits the entry into new entries, each classified on its own. pieces are trimmed and empty
+/// ones skipped, returns the new ids in the order of the pieces. fails when there are more
+/// pieces than the history can hold
+#[tauri::command]
+#[specta::specta]
+pub async fn split_entry(
+    id: &str,
+    splitter: Splitter,
+    state: tauri::State<'_, Arc<ClipboardHistory>>,
+) -> Result<Vec<String>, String> {
+    let mut lru = state.data.write().unwrap();
+    let text = lru
+        .get(id)
+        .ok_or_else(|| "entry not found".to_string())?
+        .val();
+    let pieces: Vec<&str> = match &splitter {
+        Splitter::Lines => text.lines().collect(),
+        Splitter::Delimiter(delimiter) if delimiter.is_empty() => {
+            return Err("delimiter is empty".into())
+        }
+        Splitter::Delimiter(delimiter) => text.split(delimiter.as_str()).collect(),
+        Splitter::Regex(pattern) => {
+            let regex = regex_search::compile(pattern, false).map_err(|err| match err {
+                RegexError::TooLong { max } => format!("pattern is longer than {} bytes", max),
+                RegexError::Compile(err) => err,
+            })?;
+            // an empty match would split between every character
+            if regex.is_match("") || regex.find_iter(&text).any(|m| m.is_empty()) {
+                return Err("pattern matches the empty string".into());
+            }
+            regex.split(&text).collect()
+        }
+    };
+    let pieces: Vec<&str> = pieces
+        .into_iter()
+        .map(str::trim)
+        .filter(|piece| !piece.is_empty())
+        .collect();
+    // more pieces than the history holds would evict each other and the source entry
+    if pieces.len() >= lru.capacity() {
+        return Err(format!(
+            "splitting gives {} pieces, the history holds {}",
+            pieces.len(),
+            lru.capacity()
+        ));
+    }
+    // inserted last to first so the history lists them in their original order
+    let mut ids: Vec<String> = pieces
+        .into_iter()
+        .rev()
+        .map(|piece| lru.insert(piece.to_string()))
+        .collect();
+    ids.reverse();
+    Ok(ids)
+}
+
+/// finds emails, phone numbers, urls, ips, uuids and jwts in the entry, with `insert` each match
+/// is also added to the history
+#[tauri::command]
+#[specta::specta]
+pub async fn extract_from_entry(
+    id: &str,
+    kinds: Vec<ExtractKind>,
+    insert: bool,
+    state: tauri::State<'_, Arc<ClipboardHistory>>,
+) -> Result<Vec<Extracted>, String> {
+    let mut lru = state.data.write().unwrap();
+    let text = lru
+        .get(id)
+        .ok_or_else(|| "entry not found".to_string())?
+        .val();
+    Ok(extract::extract(&text, &kinds)
+        .into_iter()
+        .map(|(kind, value)| Extracted {
+            kind,
+            id: insert.then(|| lru.insert(value.clone())),
+            value,
+        })
+        .collect())
+}
+
+/// evaluates `path` against a json entry, with `copy` the result is also written to the clipboard
+#[tauri::command]
+#[specta::specta]
+pub fn json_query(
+    id: &str,
+    path: &str,
+    copy: bool,
+    state: tauri::State<Arc<ClipboardHistory>>,
+) -> Result<String, String> {
+    let lru = state.data.read().unwrap();
+    let result = match lru.get(id) {
+        Some(Data::JsonDict { data: _, value }) => json::query(value.into_inner(), path)?,
+        Some(_) => return Err("entry is not json".into()),
+        None => return Err("entry not found".into()),
+    };
+    drop(lru);
+    let result = match result {
+        serde_json::Value::String(str) => str,
+        value => serde_json::to_string_pretty(&value).map_err(|e| e.to_string())?,
+    };
+    if copy {
+        Clipboard::new()
+            .and_then(|mut clipboard| clipboard.set_text(result.clone()))
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(result)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn decode_jwt(
+    id: &str,
+    key: Option<String>,
+    state: tauri::State<Arc<ClipboardHistory>>,
+) -> Result<DecodedJwt, String> {
+    let lru = state.data.read().unwrap();
+    match lru.get(id) {
+        Some(Data::Jwt(token)) => jwt::decode(&token, key.as_deref()),
+        Some(_) => Err("entry is not a jwt".into()),
+        None => Err("entry not found".into()),
+    }
+}
+
+/// decodes the entry and inserts the result as a new entry, returns the new id
+#[tauri::command]
+#[specta::specta]
+pub fn decode_entry(
+    id: &str,
+    codec: Codec,
+    state: tauri::State<Arc<ClipboardHistory>>,
+) -> Result<String, String> {
+    let mut lru = state.data.write().unwrap();
+    let data = lru.get(id).ok_or_else(|| "entry not found".to_string())?;
+    let decoded = codec.decode(&data.val())?;
+    Ok(lru.insert(decoded))
+}
+
+/// encodes the entry and inserts the result as a new entry, returns the new id
+#[tauri::command]
+#[specta::specta]
+pub fn encode_entry(
+    id: &str,
+    codec: Codec,
+    state: tauri::State<Arc<ClipboardHistory>>,
+) -> Result<String, String> {
+    let mut lru = state.data.write().unwrap();
+    let data = lru.get(id).ok_or_else(|| "entry not found".to_string())?;
+    Ok(lru.insert(codec.encode(&data.val())))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_settings() -> Settings {
+    settings::get()
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_settings(
+    settings: Settings,
+    state: tauri::State<Arc<ClipboardHistory>>,
+) -> Result<(), String> {
+    let similarity_search = settings.similarity_search;
+    settings::set(settings)?;
+    state
+        .data
+        .write()
+        .unwrap()
+        .set_similarity(similarity_search);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn list_detectors() -> Vec<DetectorInfo> {
+    detector::detectors()
+}
+
+// ----------------------- SNIPPETS --------------------- //
+#[tauri::command]
+#[specta::specta]
+pub fn list_snippets(
+    folder: Option<String>,
+    snippets: tauri::State<Arc<RwLock<SnippetStore>>>,
+) -> Vec<Snippet> {
+    snippets.read().unwrap().list(folder.as_deref())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn list_snippet_folders(snippets: tauri::State<Arc<RwLock<SnippetStore>>>) -> Vec<String> {
+    snippets.read().unwrap().folders()
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn create_snippet(
+    name: String,
+    text: String,
+    abbreviation: Option<String>,
+    folder: Option<String>,
+    snippets: tauri::State<Arc<RwLock<SnippetStore>>>,
+) -> Result<Snippet, String> {
+    let mut store = snippets.write().unwrap();
+    store.create(name, text, abbreviation, folder)
+}
+
+/// saves a history entry as a snippet, the entry itself stays in the history
+#[tauri::command]
+#[specta::specta]
+pub fn snippet_from_entry(
+    id: &str,
+    name: String,
+    abbreviation: Option<String>,
+    folder: Option<String>,
+    state: tauri::State<Arc<ClipboardHistory>>,
+    snippets: tauri::State<Arc<RwLock<SnippetStore>>>,
+) -> Result<Snippet, String> {
+    let text = state
+        .data
+        .read()
+        .unwrap()
+        .get(id)
+        .ok_or_else(|| "entry not found".to_string())?
+        .val();
+    let mut store = snippets.write().unwrap();
+    store.create(name, text, abbreviation, folder)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_snippet(
+    snippet: Snippet,
+    snippets: tauri::State<Arc<RwLock<SnippetStore>>>,
+) -> Result<(), String> {
+    snippets.write().unwrap().update(snippet)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn delete_snippet(
+    id: &str,
+    snippets: tauri::State<Arc<RwLock<SnippetStore>>>,
+) -> Result<(), String> {
+    snippets.write().unwrap().delete(id)
+}
+
+/// labels the user has to fill in before `copy_snippet` can expand the snippet, `key` is a
+/// snippet id or abbreviation
+#[tauri::command]
+#[specta::specta]
+pub fn snippet_inputs(
+    key: &str,
+    snippets: tauri::State<Arc<RwLock<SnippetStore>>>,
+) -> Result<Vec<String>, String> {
+    let store = snippets.read().unwrap();
+    let snippet = store
+        .get(key)
+        .ok_or_else(|| "snippet not found".to_string())?;
+    Ok(template::inputs(&snippet.text))
+}
+
+/// expands the snippet placeholders with `inputs` answering the labels from `snippet_inputs`
+/// and writes the result to the clipboard, which is also returned. `key` is a snippet id or
+/// abbreviation
+#[tauri::command]
+#[specta::specta]
+pub fn copy_snippet(
+    key: &str,
+    inputs: HashMap<String, String>,
+    snippets: tauri::State<Arc<RwLock<SnippetStore>>>,
+    history: tauri::State<Arc<ClipboardHistory>>,
+) -> Result<String, String> {
+    let text = snippets
+        .read()
+        .unwrap()
+        .get(key)
+        .map(|snippet| snippet.text.clone())
+        .ok_or_else(|| "snippet not found".to_string())?;
+    let mut clipboard = Clipboard::new().map_err(|e| e.to_string())?;
+    let context = template::Context {
+        now: chrono::Local::now(),
+        clipboard: match template::uses_clipboard(&text) {
+            true => clipboard.get_text().unwrap_or_default(),
+            false => String::new(),
+        },
+        inputs,
+    };
+    let expanded = template::render(&text, &context)?;
+    // snippets stay out of the history, the poller skips what it already saw
+    let mut last_value = history.last_value.write().unwrap();
+    clipboard
+        .set_text(expanded.clone())
+        .map_err(|e| e.to_string())?;
+    *last_value = expanded.clone();
+    Ok(expanded)
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn get_pool_clipboard_state(state: tauri::State<Arc<RwLock<PoolClipboard>>>) -> bool {
@@ -153,3 +1006,57 @@ pub fn set_pool_clipboard_state(state: tauri::State<Arc<RwLock<PoolClipboard>>>,
     let mut pool_clipboard = state.write().unwrap();
     pool_clipboard.set(value);
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_stream_cancel() {
+        let history = ClipboardHistory::new();
+        for i in 0..5 {
+            history
+                .data
+                .write()
+                .unwrap()
+                .insert(format!("deploy {}", i));
+        }
+        let query = query::parse("deploy").unwrap();
+        let matcher = SkimMatcherV2::default();
+        let lru = history.data.read().unwrap();
+        assert_eq!(rank(&lru, &query, &matcher, || false).unwrap().len(), 5);
+        assert!(rank(&lru, &query, &matcher, || true).is_none());
+        drop(lru);
+
+        let search = SearchState::default();
+        let mut events = vec![];
+        stream(&history, &search, 0, &query, 2, |event| {
+            events.push(event);
+            true
+        });
+        assert_eq!(events.len(), 4);
+        assert!(matches!(events[3], SearchEvent::Done { total: 5, .. }));
+
+        // a newer search started before ranking finished
+        search.generation.store(1, Ordering::SeqCst);
+        let mut sent = 0;
+        stream(&history, &search, 0, &query, 2, |_| {
+            sent += 1;
+            true
+        });
+        assert_eq!(sent, 0);
+
+        // and one started after the first batch went out
+        let mut events = vec![];
+        stream(&history, &search, 1, &query, 2, |event| {
+            events.push(event);
+            search.generation.fetch_add(1, Ordering::SeqCst);
+            true
+        });
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            events[0],
+            SearchEvent::Batch { generation: 1, .. }
+        ));
+    }
+}
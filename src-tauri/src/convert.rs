@@ -0,0 +1,157 @@
+use crate::data::ProgrammingLanguage;
+use crate::formatter::FormatError;
+use crate::json;
+use serde_json::{Map, Value};
+
+fn error(lang: ProgrammingLanguage, message: impl ToString) -> FormatError {
+    FormatError::Parse {
+        lang,
+        message: message.to_string(),
+    }
+}
+
+/// csv cells are untyped, numbers and booleans are recovered on a best effort basis
+fn csv_cell(cell: &str) -> Value {
+    let digits = cell.trim_start_matches('-');
+    // zip codes and ids like `007` lose their zeros as numbers
+    let leading_zero = digits.len() > 1 && digits.starts_with('0') && !digits.starts_with("0.");
+    match cell {
+        "" => Value::Null,
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        _ if leading_zero => Value::String(cell.into()),
+        _ => cell
+            .parse::<i64>()
+            .map(Value::from)
+            .ok()
+            .or_else(|| {
+                cell.parse::<f64>()
+                    .ok()
+                    .filter(|n| n.is_finite())
+                    .map(Value::from)
+            })
+            .unwrap_or_else(|| Value::String(cell.into())),
+    }
+}
+
+fn from_csv(text: &str) -> Result<Value, FormatError> {
+    let lang = ProgrammingLanguage::CSV;
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(text.as_bytes());
+    let headers = reader.headers().map_err(|e| error(lang, e))?.clone();
+    let mut rows = vec![];
+    for record in reader.records() {
+        let record = record.map_err(|e| error(lang, e))?;
+        // cells missing from short rows are null like empty ones
+        let row: Map<String, Value> = headers
+            .iter()
+            .enumerate()
+            .map(|(i, key)| (key.to_string(), record.get(i).map_or(Value::Null, csv_cell)))
+            .collect();
+        rows.push(Value::Object(row));
+    }
+    Ok(Value::Array(rows))
+}
+
+fn to_csv(value: &Value) -> Result<String, FormatError> {
+    let lang = ProgrammingLanguage::CSV;
+    let not_flat = || error(lang, "csv needs an array of flat objects");
+    let rows = value.as_array().ok_or_else(not_flat)?;
+    let mut headers: Vec<&String> = vec![];
+    for row in rows {
+        for key in row.as_object().ok_or_else(not_flat)?.keys() {
+            if !headers.contains(&key) {
+                headers.push(key);
+            }
+        }
+    }
+    let mut writer = csv::Writer::from_writer(vec![]);
+    writer.write_record(&headers).map_err(|e| error(lang, e))?;
+    for row in rows {
+        let mut record = vec![];
+        for key in &headers {
+            record.push(match row.get(key.as_str()) {
+                None | Some(Value::Null) => String::new(),
+                Some(Value::String(str)) => str.clone(),
+                Some(Value::Array(_) | Value::Object(_)) => return Err(not_flat()),
+                Some(value) => value.to_string(),
+            });
+        }
+        writer.write_record(&record).map_err(|e| error(lang, e))?;
+    }
+    let bytes = writer.into_inner().map_err(|e| error(lang, e))?;
+    String::from_utf8(bytes).map_err(|e| error(lang, e))
+}
+
+pub fn parse(text: &str, lang: ProgrammingLanguage) -> Result<Value, FormatError> {
+    match lang {
+        ProgrammingLanguage::JSON => json::parse(text).ok_or_else(|| error(lang, "invalid json")),
+        ProgrammingLanguage::YAML => serde_yaml::from_str(text).map_err(|e| error(lang, e)),
+        ProgrammingLanguage::TOML => toml::from_str(text).map_err(|e| error(lang, e.message())),
+        ProgrammingLanguage::CSV => from_csv(text),
+        lang => Err(FormatError::Unsupported(lang)),
+    }
+}
+
+pub fn serialize(value: &Value, lang: ProgrammingLanguage) -> Result<String, FormatError> {
+    match lang {
+        ProgrammingLanguage::JSON => {
+            serde_json::to_string_pretty(value).map_err(|e| error(lang, e))
+        }
+        ProgrammingLanguage::YAML => serde_yaml::to_string(value).map_err(|e| error(lang, e)),
+        ProgrammingLanguage::TOML => toml::to_string_pretty(value).map_err(|e| error(lang, e)),
+        ProgrammingLanguage::CSV => to_csv(value),
+        lang => Err(FormatError::Unsupported(lang)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ProgrammingLanguage::{CSV, JSON, TOML, YAML};
+
+    fn convert(
+        text: &str,
+        from: ProgrammingLanguage,
+        to: ProgrammingLanguage,
+    ) -> Result<String, FormatError> {
+        serialize(&parse(text, from)?, to)
+    }
+
+    #[test]
+    fn test_convert() {
+        let json = r#"{"name": "app", "ports": [80, 443], "meta": {"team": "infra"}}"#;
+        let yaml = convert(json, JSON, YAML).unwrap();
+        assert_eq!(
+            yaml,
+            "name: app\nports:\n- 80\n- 443\nmeta:\n  team: infra\n"
+        );
+        let toml = convert(&yaml, YAML, TOML).unwrap();
+        assert_eq!(parse(&toml, TOML).unwrap(), parse(json, JSON).unwrap());
+        assert!(convert("[1, null]", JSON, TOML).is_err());
+    }
+
+    #[test]
+    fn test_csv() {
+        let json = r#"[{"id": 1, "name": "a, b", "ok": true}, {"id": 2, "name": "c"}]"#;
+        let csv = convert(json, JSON, CSV).unwrap();
+        assert_eq!(csv, "id,name,ok\n1,\"a, b\",true\n2,c,\n");
+        assert_eq!(
+            parse(&csv, CSV).unwrap(),
+            serde_json::json!([
+                {"id": 1, "name": "a, b", "ok": true},
+                {"id": 2, "name": "c", "ok": null}
+            ])
+        );
+        assert_eq!(
+            parse("zip,n,note\n007,-0.5,x\n10,nan\n", CSV).unwrap(),
+            serde_json::json!([
+                {"zip": "007", "n": -0.5, "note": "x"},
+                {"zip": 10, "n": "nan", "note": null}
+            ])
+        );
+        assert!(convert(r#"[{"a": [1]}]"#, JSON, CSV).is_err());
+        assert!(convert(r#"{"a": 1}"#, JSON, CSV).is_err());
+    }
+}
@@ -1,16 +1,25 @@
+use crate::codec::Codec;
+use crate::detector;
+use crate::jwt;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use specta::Type;
+use std::str::FromStr as _;
+use std::sync::LazyLock;
 use strum_macros::Display;
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-struct MyValue(Value);
+pub struct MyValue(Value);
 impl MyValue {
-    #[allow(dead_code)]
     pub fn into_inner(&self) -> &Value {
         &self.0
     }
 }
+impl From<Value> for MyValue {
+    fn from(value: Value) -> Self {
+        Self(value)
+    }
+}
 
 impl Type for MyValue {
     fn reference(
@@ -52,6 +61,10 @@ pub enum ProgrammingLanguage {
     CSS,
     SQL,
     JSON,
+    YAML,
+    TOML,
+    XML,
+    CSV,
     Markdown,
     String,
 }
@@ -59,8 +72,24 @@ pub enum ProgrammingLanguage {
 #[serde(tag = "tag", content = "content")]
 pub enum Data {
     Email(String),
-    PhoneNumber(String),
-    // JsonDict(MyValue),
+    PhoneNumber {
+        data: String,
+        /// normalized E.164 form, eg `+14155552671`
+        e164: String,
+    },
+    Url(String),
+    Ip(String),
+    Uuid(String),
+    Jwt(String),
+    Encoded {
+        data: String,
+        codec: Codec,
+    },
+    /// json parsed once at ingest, `data` keeps the original text
+    JsonDict {
+        data: String,
+        value: MyValue,
+    },
     Code {
         data: String,
         lang: ProgrammingLanguage,
@@ -70,8 +99,13 @@ impl Data {
     pub fn hash(&self) -> String {
         match self {
             Data::Email(email) => blake3::hash(email.as_bytes()),
-            Data::PhoneNumber(phone) => blake3::hash(phone.as_bytes()),
-            // Data::JsonDict(json) => blake3::hash(json.into_inner().to_string().as_bytes()),
+            Data::PhoneNumber { data, e164: _ } => blake3::hash(data.as_bytes()),
+            Data::Url(url) => blake3::hash(url.as_bytes()),
+            Data::Ip(ip) => blake3::hash(ip.as_bytes()),
+            Data::Uuid(uuid) => blake3::hash(uuid.as_bytes()),
+            Data::Jwt(token) => blake3::hash(token.as_bytes()),
+            Data::Encoded { data, codec: _ } => blake3::hash(data.as_bytes()),
+            Data::JsonDict { data, value: _ } => blake3::hash(data.as_bytes()),
             Data::Code { data, lang: _ } => blake3::hash(data.as_bytes()),
         }
         .to_hex()
@@ -80,42 +114,139 @@ impl Data {
     pub fn val(&self) -> String {
         match self {
             Data::Email(str) => str.clone(),
-            Data::PhoneNumber(str) => str.clone(),
-            // Data::JsonDict(str) => serde_json::to_string(&str).unwrap_or("err str".into()),
+            Data::PhoneNumber { data, e164: _ } => data.clone(),
+            Data::Url(str) => str.clone(),
+            Data::Ip(str) => str.clone(),
+            Data::Uuid(str) => str.clone(),
+            Data::Jwt(str) => str.clone(),
+            Data::Encoded { data, codec: _ } => data.clone(),
+            Data::JsonDict { data, value: _ } => data.clone(),
             Data::Code { data, lang: _ } => data.clone(),
         }
     }
+    pub fn lang(&self) -> Option<ProgrammingLanguage> {
+        match self {
+            Data::JsonDict { data: _, value: _ } => Some(ProgrammingLanguage::JSON),
+            Data::Code { data: _, lang } => Some(*lang),
+            _ => None,
+        }
+    }
+    /// lowercase kind name as used by `type:` in search queries, matching `DetectorKind`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Data::Email(_) => "email",
+            Data::PhoneNumber { data: _, e164: _ } => "phonenumber",
+            Data::Url(_) => "url",
+            Data::Ip(_) => "ip",
+            Data::Uuid(_) => "uuid",
+            Data::Jwt(_) => "jwt",
+            Data::Encoded { data: _, codec: _ } => "encoded",
+            Data::JsonDict { data: _, value: _ } => "json",
+            Data::Code { data: _, lang: _ } => "code",
+        }
+    }
+    /// copy safe to send to the ui, secrets are masked
+    pub fn masked(self) -> Data {
+        match self {
+            Data::Jwt(token) => Data::Jwt(jwt::mask(&token)),
+            data => data,
+        }
+    }
 }
-fn is_valid_email(email: &str) -> bool {
-    let email_regex = Regex::new(r"(?i)^[a-z0-9._%+-]+@[a-z0-9.-]+\.[a-z]{2,}$").unwrap();
+static EMAIL_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)^[a-z0-9._%+-]+@[a-z0-9.-]+\.[a-z]{2,}$").unwrap());
 
-    email_regex.is_match(email)
+pub(crate) fn is_valid_email(email: &str) -> bool {
+    EMAIL_REGEX.is_match(email)
+}
+pub(crate) fn is_valid_url(url: &str) -> bool {
+    url::Url::parse(url)
+        .map(|url| matches!(url.scheme(), "http" | "https" | "ftp") && url.host().is_some())
+        .unwrap_or(false)
+        && !url.chars().any(char::is_whitespace)
+}
+pub(crate) fn is_valid_ip(ip: &str) -> bool {
+    ip.parse::<std::net::IpAddr>().is_ok()
 }
-fn is_valid_phone_number(phone: &str) -> bool {
-    let phone_regex =
-        Regex::new(r"^\+?[0-9]{1,3}?[-. ]?\(?[0-9]{2,4}\)?[-. ]?[0-9]{3,4}[-. ]?[0-9]{4}$")
-            .unwrap();
-    phone_regex.is_match(phone)
+pub(crate) fn is_valid_uuid(uuid: &str) -> bool {
+    uuid.len() == 36 && uuid::Uuid::try_parse(uuid).is_ok()
+}
+/// returns the E.164 form when `phone` is a valid number, numbers without a
+/// country code are read in `region`
+pub(crate) fn normalize_phone_number(phone: &str, region: &str) -> Option<String> {
+    let phone = phone.trim();
+    let digits = phone.chars().filter(char::is_ascii_digit).count();
+    if phone.len() > 32
+        || !(7..=15).contains(&digits)
+        || !phone
+            .chars()
+            .all(|c| c.is_ascii_digit() || " +-().".contains(c))
+    {
+        return None;
+    }
+    let region = phonenumber::country::Id::from_str(region).ok();
+    let number = phonenumber::parse(region, phone).ok()?;
+    if !number.is_valid() {
+        return None;
+    }
+    Some(number.format().mode(phonenumber::Mode::E164).to_string())
 }
 
 impl From<String> for Data {
     fn from(value: String) -> Self {
-        let str_len = value.len();
-        if str_len <= 15 && is_valid_phone_number(&value) {
-            Data::PhoneNumber(value)
-        } else if str_len < 256 && is_valid_email(&value) {
-            Data::Email(value)
-        } else {
-            match serde_json::from_str::<serde_json::Value>(&value) {
-                Ok(serde_json::Value::Object(_)) => Data::Code {
-                    data: value,
-                    lang: ProgrammingLanguage::JSON,
-                },
-                _ => Data::Code {
-                    data: value,
-                    lang: ProgrammingLanguage::String,
-                },
+        detector::classify(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_phone_number() {
+        assert_eq!(
+            normalize_phone_number("+1 (415) 555-2671", "US").as_deref(),
+            Some("+14155552671")
+        );
+        assert_eq!(
+            normalize_phone_number("(415) 555-2671", "US").as_deref(),
+            Some("+14155552671")
+        );
+        assert_eq!(
+            normalize_phone_number("094493 52583", "IN").as_deref(),
+            Some("+919449352583")
+        );
+        assert_eq!(normalize_phone_number("1234567890", "US"), None);
+        assert_eq!(normalize_phone_number("2024-10-01", "US"), None);
+    }
+
+    #[test]
+    fn test_classify() {
+        let classify = |value: &str| Data::from(value.to_string());
+        assert_eq!(
+            classify("192.168.100.200"),
+            Data::Ip("192.168.100.200".into())
+        );
+        assert_eq!(classify("::1"), Data::Ip("::1".into()));
+        assert_eq!(
+            classify("https://example.com/a?b=1"),
+            Data::Url("https://example.com/a?b=1".into())
+        );
+        assert_eq!(
+            classify("67e55044-10b1-426f-9247-bb680e5fe0c8"),
+            Data::Uuid("67e55044-10b1-426f-9247-bb680e5fe0c8".into())
+        );
+        assert_eq!(
+            classify("nithin@gmail.com"),
+            Data::Email("nithin@gmail.com".into())
+        );
+        assert!(matches!(classify("[1, 2]"), Data::JsonDict { .. }));
+        assert!(matches!(
+            classify("not a url"),
+            Data::Code {
+                lang: ProgrammingLanguage::String,
+                ..
             }
-        }
+        ));
     }
 }
@@ -0,0 +1,196 @@
+use crate::codec::Codec;
+use crate::data::{
+    is_valid_email, is_valid_ip, is_valid_url, is_valid_uuid, normalize_phone_number, Data,
+    ProgrammingLanguage,
+};
+use crate::json;
+use crate::jwt;
+use crate::settings::{self, Settings};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::LazyLock;
+use strum_macros::Display;
+
+#[derive(Debug, Display, Serialize, Deserialize, Clone, Copy, Type, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum DetectorKind {
+    Ip,
+    PhoneNumber,
+    Email,
+    Url,
+    Uuid,
+    Jwt,
+    Encoded,
+    Json,
+}
+
+pub struct Detector {
+    pub kind: DetectorKind,
+    /// detectors run in ascending priority, the first match wins
+    pub priority: u8,
+    detect: fn(&str, &Settings) -> Option<Data>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DetectorInfo {
+    pub kind: DetectorKind,
+    pub priority: u8,
+    pub enabled: bool,
+}
+
+static REGISTRY: LazyLock<Vec<Detector>> = LazyLock::new(|| {
+    let mut detectors = vec![
+        // ip addresses go first, `192.168.100.200` would pass as a phone number
+        Detector {
+            kind: DetectorKind::Ip,
+            priority: 10,
+            detect: |value, _| is_valid_ip(value).then(|| Data::Ip(value.into())),
+        },
+        Detector {
+            kind: DetectorKind::PhoneNumber,
+            priority: 20,
+            detect: |value, settings| {
+                normalize_phone_number(value, &settings.phone_region).map(|e164| {
+                    Data::PhoneNumber {
+                        data: value.into(),
+                        e164,
+                    }
+                })
+            },
+        },
+        Detector {
+            kind: DetectorKind::Email,
+            priority: 30,
+            detect: |value, _| {
+                (value.len() < 256 && is_valid_email(value)).then(|| Data::Email(value.into()))
+            },
+        },
+        Detector {
+            kind: DetectorKind::Url,
+            priority: 40,
+            detect: |value, _| {
+                (value.len() < 2048 && is_valid_url(value)).then(|| Data::Url(value.into()))
+            },
+        },
+        Detector {
+            kind: DetectorKind::Uuid,
+            priority: 50,
+            detect: |value, _| is_valid_uuid(value).then(|| Data::Uuid(value.into())),
+        },
+        Detector {
+            kind: DetectorKind::Jwt,
+            priority: 60,
+            detect: |value, _| jwt::is_jwt(value).then(|| Data::Jwt(value.into())),
+        },
+        Detector {
+            kind: DetectorKind::Encoded,
+            priority: 70,
+            detect: |value, _| {
+                Codec::detect(value).map(|codec| Data::Encoded {
+                    data: value.into(),
+                    codec,
+                })
+            },
+        },
+        Detector {
+            kind: DetectorKind::Json,
+            priority: 80,
+            detect: |value, _| {
+                json::parse(value).map(|json| Data::JsonDict {
+                    data: value.into(),
+                    value: json.into(),
+                })
+            },
+        },
+    ];
+    detectors.sort_by_key(|detector| detector.priority);
+    detectors
+});
+
+impl Detector {
+    fn priority(&self, settings: &Settings) -> u8 {
+        settings
+            .detector_priorities
+            .get(&self.kind)
+            .copied()
+            .unwrap_or(self.priority)
+    }
+}
+
+/// every detector in the order `classify` runs them, overrides tie with the built in order
+fn ordered(settings: &Settings) -> Vec<&'static Detector> {
+    let mut detectors: Vec<&Detector> = REGISTRY.iter().collect();
+    detectors.sort_by_key(|detector| detector.priority(settings));
+    detectors
+}
+
+pub fn detectors() -> Vec<DetectorInfo> {
+    let settings = settings::get();
+    ordered(&settings)
+        .into_iter()
+        .map(|detector| DetectorInfo {
+            kind: detector.kind,
+            priority: detector.priority(&settings),
+            enabled: !settings.disabled_detectors.contains(&detector.kind),
+        })
+        .collect()
+}
+
+/// runs the enabled detectors in priority order, anything unmatched is stored as a plain string
+pub fn classify(value: String) -> Data {
+    classify_with(value, &settings::get())
+}
+
+fn classify_with(value: String, settings: &Settings) -> Data {
+    ordered(settings)
+        .into_iter()
+        .filter(|detector| !settings.disabled_detectors.contains(&detector.kind))
+        .find_map(|detector| (detector.detect)(&value, settings))
+        .unwrap_or(Data::Code {
+            data: value,
+            lang: ProgrammingLanguage::String,
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_registry_order() {
+        let priorities: Vec<_> = REGISTRY.iter().map(|d| d.priority).collect();
+        let mut sorted = priorities.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(priorities, sorted);
+
+        let detect = |kind: DetectorKind, value: &str| {
+            let detector = REGISTRY.iter().find(|d| d.kind == kind).unwrap();
+            (detector.detect)(value, &Settings::default())
+        };
+        assert!(detect(DetectorKind::Email, "a@b.co").is_some());
+        assert!(detect(DetectorKind::Email, "https://a.co").is_none());
+        assert!(detect(DetectorKind::Url, "https://a.co").is_some());
+    }
+
+    #[test]
+    fn test_priority_override() {
+        // a url with escapes is also a percent encoded string
+        let value = "https://a.co/%7Euser";
+        let mut settings = Settings::default();
+        assert!(matches!(
+            classify_with(value.into(), &settings),
+            Data::Url(_)
+        ));
+        settings
+            .detector_priorities
+            .insert(DetectorKind::Encoded, 0);
+        assert!(matches!(
+            classify_with(value.into(), &settings),
+            Data::Encoded { .. }
+        ));
+        let order: Vec<_> = ordered(&settings).iter().map(|d| d.kind).collect();
+        assert_eq!(order[..2], [DetectorKind::Encoded, DetectorKind::Ip]);
+    }
+}
@@ -0,0 +1,189 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use similar::{ChangeTag, TextDiff};
+use specta::Type;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Type, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffMode {
+    Line,
+    Word,
+    /// structural diff of two json values, key order is ignored
+    Json,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Type, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Equal,
+    Insert,
+    Delete,
+    /// only used by json diffs, for a value that changed in place
+    Replace,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type, PartialEq)]
+pub struct TextChange {
+    pub kind: ChangeKind,
+    pub value: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type, PartialEq)]
+pub struct JsonChange {
+    /// JSONPath of the changed value, eg `$.spec.ports[0]`
+    pub path: String,
+    pub kind: ChangeKind,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type, PartialEq)]
+#[serde(tag = "tag", content = "content")]
+pub enum Diff {
+    Text {
+        /// unified diff for line mode, `[-old-]{+new+}` markup for word mode
+        unified: String,
+        changes: Vec<TextChange>,
+    },
+    Json(Vec<JsonChange>),
+}
+
+fn kind(tag: ChangeTag) -> ChangeKind {
+    match tag {
+        ChangeTag::Equal => ChangeKind::Equal,
+        ChangeTag::Insert => ChangeKind::Insert,
+        ChangeTag::Delete => ChangeKind::Delete,
+    }
+}
+
+pub fn diff_text(a: &str, b: &str, mode: DiffMode) -> Diff {
+    let diff = match mode {
+        DiffMode::Word => TextDiff::from_words(a, b),
+        _ => TextDiff::from_lines(a, b),
+    };
+    let changes: Vec<TextChange> = diff
+        .iter_all_changes()
+        .map(|change| TextChange {
+            kind: kind(change.tag()),
+            value: change.value().to_string(),
+        })
+        .collect();
+    let unified = match mode {
+        DiffMode::Word => changes
+            .iter()
+            .map(|change| match change.kind {
+                ChangeKind::Equal | ChangeKind::Replace => change.value.clone(),
+                ChangeKind::Insert => format!("{{+{}+}}", change.value),
+                ChangeKind::Delete => format!("[-{}-]", change.value),
+            })
+            .collect(),
+        _ => diff.unified_diff().header("a", "b").to_string(),
+    };
+    Diff::Text { unified, changes }
+}
+
+/// jsonpath of `key` under `path`, bracket notation unless the key is a plain identifier
+fn child(path: &str, key: &str) -> String {
+    let plain = key.chars().next().is_some_and(|c| !c.is_ascii_digit())
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+    if plain {
+        format!("{}.{}", path, key)
+    } else {
+        let key = key.replace('\\', "\\\\").replace('\'', "\\'");
+        format!("{}['{}']", path, key)
+    }
+}
+
+fn walk(path: String, a: Option<&Value>, b: Option<&Value>, changes: &mut Vec<JsonChange>) {
+    match (a, b) {
+        (Some(Value::Object(a)), Some(Value::Object(b))) => {
+            for (key, value) in a {
+                walk(child(&path, key), Some(value), b.get(key), changes);
+            }
+            for (key, value) in b.iter().filter(|(key, _)| !a.contains_key(*key)) {
+                walk(child(&path, key), None, Some(value), changes);
+            }
+        }
+        (Some(Value::Array(a)), Some(Value::Array(b))) => {
+            for i in 0..a.len().max(b.len()) {
+                walk(format!("{}[{}]", path, i), a.get(i), b.get(i), changes);
+            }
+        }
+        (a, b) if a == b => {}
+        (a, b) => changes.push(JsonChange {
+            path,
+            kind: match (a, b) {
+                (None, _) => ChangeKind::Insert,
+                (_, None) => ChangeKind::Delete,
+                _ => ChangeKind::Replace,
+            },
+            old: a.map(Value::to_string),
+            new: b.map(Value::to_string),
+        }),
+    }
+}
+
+/// only differing paths are reported
+pub fn diff_json(a: &Value, b: &Value) -> Diff {
+    let mut changes = vec![];
+    walk("$".into(), Some(a), Some(b), &mut changes);
+    Diff::Json(changes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_text() {
+        let Diff::Text { unified, changes } = diff_text("a\nb\nc\n", "a\nB\nc\n", DiffMode::Line)
+        else {
+            panic!("expected text diff")
+        };
+        assert!(unified.contains("-b\n+B\n"));
+        assert_eq!(changes.len(), 4);
+
+        let Diff::Text { unified, .. } = diff_text("the quick fox", "the slow fox", DiffMode::Word)
+        else {
+            panic!("expected text diff")
+        };
+        assert_eq!(unified, "the [-quick-]{+slow+} fox");
+    }
+
+    #[test]
+    fn test_diff_json() {
+        let a = json!({"a": 1, "b": [1, 2], "c": {"d": true}});
+        let b = json!({"c": {"d": true}, "b": [1], "a": 2, "e": null});
+        assert_eq!(diff_json(&a, &a.clone()), Diff::Json(vec![]));
+        let Diff::Json(changes) = diff_json(&a, &b) else {
+            panic!("expected json diff")
+        };
+        let paths: Vec<_> = changes.iter().map(|c| (c.path.as_str(), c.kind)).collect();
+        assert_eq!(
+            paths,
+            vec![
+                ("$.a", ChangeKind::Replace),
+                ("$.b[1]", ChangeKind::Delete),
+                ("$.e", ChangeKind::Insert)
+            ]
+        );
+
+        let a = json!({"a.b": 1, "has space": {"[x]": 1, "it's": 1}});
+        let b = json!({"a.b": 2, "has space": {"[x]": 2, "it's": 2}});
+        let Diff::Json(changes) = diff_json(&a, &b) else {
+            panic!("expected json diff")
+        };
+        let paths: Vec<_> = changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "$['a.b']",
+                "$['has space']['[x]']",
+                r"$['has space']['it\'s']"
+            ]
+        );
+    }
+}
@@ -1,7 +1,11 @@
 use std::sync::{Arc, RwLock, Weak};
+/// older values beyond this are dropped
+const MAX_REVISIONS: usize = 50;
 #[derive(Debug)]
 pub struct Node<T> {
     val: T,
+    /// previous values, oldest first
+    revisions: Vec<T>,
     next: Option<Arc<RwLock<Node<T>>>>,
     prev: Option<Weak<RwLock<Node<T>>>>,
 }
@@ -9,6 +13,7 @@ impl<T: Clone> Node<T> {
     pub fn new(val: T) -> Self {
         Self {
             val,
+            revisions: Vec::new(),
             next: None,
             prev: None,
         }
@@ -16,10 +21,27 @@ impl<T: Clone> Node<T> {
     pub fn val(&self) -> T {
         self.val.clone()
     }
+    pub fn prev(&self) -> Option<Arc<RwLock<Node<T>>>> {
+        self.prev.as_ref().and_then(Weak::upgrade)
+    }
 }
 impl<T: Clone> Node<T> {
+    /// replaces the value, the previous value is kept as a revision
     pub fn set_value(&mut self, value: T) {
-        self.val = value
+        let prev = std::mem::replace(&mut self.val, value);
+        self.revisions.push(prev);
+        if self.revisions.len() > MAX_REVISIONS {
+            self.revisions.remove(0);
+        }
+    }
+    pub fn revisions(&self) -> &[T] {
+        &self.revisions
+    }
+    /// restores a revision, the current value becomes the newest revision so a revert can be undone
+    pub fn revert(&mut self, index: usize) -> Option<()> {
+        let value = self.revisions.get(index)?.clone();
+        self.set_value(value);
+        Some(())
     }
 }
 #[derive(Debug)]
@@ -42,6 +64,9 @@ where
     pub fn peak_front(&self) -> Option<Arc<RwLock<Node<T>>>> {
         self.head.clone()
     }
+    pub fn peak_back(&self) -> Option<Arc<RwLock<Node<T>>>> {
+        self.tail.clone()
+    }
     pub fn push_front_rc(&mut self, node: Arc<RwLock<Node<T>>>) {
         {
             let mut node = node.write().unwrap();
@@ -121,41 +146,27 @@ where
         self.len = (self.len.max(1) - 1).max(0);
         node
     }
+    #[allow(dead_code)]
     pub fn pop_back(&mut self) -> Option<T> {
-        let node = match self.tail.take() {
-            Some(node) => Some(node.read().unwrap().val.clone()),
-            None => None,
-        };
-        if self.len == 1 {
-            self.head = None
-        }
-        self.len = (self.len.max(1) - 1).max(0);
-        node
+        let node = self.tail.clone()?;
+        let val = node.read().unwrap().val.clone();
+        self.delete(node);
+        Some(val)
     }
     pub fn delete(&mut self, node: Arc<RwLock<Node<T>>>) {
-        let node = node.write().unwrap();
-        let prev = node.prev.clone();
-        let next = node.next.clone();
+        let mut node = node.write().unwrap();
+        let prev = node.prev.take().and_then(|prev| prev.upgrade());
+        let next = node.next.take();
         self.len -= 1;
-        match (prev, next) {
-            (None, None) => {
-                self.head = None;
-                self.tail = None
-            }
-            (None, Some(node)) => {
-                self.head = Some(node.clone());
-            }
-            (Some(prev), None) => match prev.upgrade() {
-                Some(prev) => self.tail = Some(prev.clone()),
-                None => self.tail = None,
-            },
-            (Some(prev), Some(next)) => {
-                next.write().unwrap().prev = Some(prev.clone());
-                match prev.upgrade() {
-                    Some(prev) => prev.write().unwrap().next = Some(next.clone()),
-                    None => self.head = Some(next.clone()),
-                }
-            }
+        // the neighbours must stop pointing at the node, a stale `next` on the new tail would
+        // link the node back in when it is pushed to the front again
+        match &prev {
+            Some(prev) => prev.write().unwrap().next = next.clone(),
+            None => self.head = next.clone(),
+        }
+        match next {
+            Some(next) => next.write().unwrap().prev = prev.as_ref().map(Arc::downgrade),
+            None => self.tail = prev,
         }
     }
     pub fn len(&self) -> usize {
@@ -225,6 +236,36 @@ fn test_push_and_pop_front() {
     assert_eq!(list.len(), 0);
 }
 
+#[test]
+fn test_delete_tail() {
+    let mut list = DoubleLinkedList::new();
+    list.push_front(1);
+    list.push_front(2);
+    list.push_front(3);
+    let tail = list.tail.clone().unwrap();
+    list.delete(tail.clone());
+    list.push_front_rc(tail);
+    assert_eq!(list.iter().collect::<Vec<_>>(), vec![1, 3, 2]);
+    assert_eq!(list.pop_back(), Some(2));
+    assert_eq!(list.iter().collect::<Vec<_>>(), vec![1, 3]);
+    assert_eq!(list.pop_back(), Some(3));
+    assert_eq!(list.pop_back(), Some(1));
+    assert_eq!(list.pop_back(), None);
+    assert_eq!(list.len(), 0);
+}
+
+#[test]
+fn test_revisions() {
+    let mut node = Node::new(1);
+    node.set_value(2);
+    node.set_value(3);
+    assert_eq!(node.revisions(), &[1, 2]);
+    node.revert(0).unwrap();
+    assert_eq!(node.val(), 1);
+    assert_eq!(node.revisions(), &[1, 2, 3]);
+    assert!(node.revert(10).is_none());
+}
+
 #[test]
 fn test_multithreaded_push_and_pop() {
     use std::thread;
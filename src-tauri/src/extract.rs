@@ -0,0 +1,162 @@
+use crate::data::{
+    is_valid_email, is_valid_ip, is_valid_url, is_valid_uuid, normalize_phone_number,
+};
+use crate::jwt;
+use crate::settings;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::LazyLock;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Type, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExtractKind {
+    Email,
+    PhoneNumber,
+    Url,
+    Ip,
+    Uuid,
+    Jwt,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type, PartialEq)]
+pub struct Extracted {
+    pub kind: ExtractKind,
+    pub value: String,
+    /// id of the inserted entry when extraction was asked to insert
+    pub id: Option<String>,
+}
+
+// candidates are found with loose patterns and then confirmed by the same checks `Data::from` uses
+static CANDIDATES: LazyLock<Vec<(ExtractKind, Regex)>> = LazyLock::new(|| {
+    [
+        (
+            ExtractKind::Email,
+            r"(?i)[a-z0-9._%+-]+@[a-z0-9.-]+\.[a-z]{2,}",
+        ),
+        (ExtractKind::Url, r#"(?i)\b(?:https?|ftp)://[^\s<>"'`]+"#),
+        (
+            ExtractKind::Uuid,
+            r"(?i)\b[0-9a-f]{8}(?:-[0-9a-f]{4}){3}-[0-9a-f]{12}\b",
+        ),
+        (ExtractKind::Ip, r"\b(?:\d{1,3}\.){3}\d{1,3}\b"),
+        (ExtractKind::Ip, r"(?i)(?:[0-9a-f]{0,4}:){2,7}[0-9a-f]{0,4}"),
+        (ExtractKind::PhoneNumber, r"\+?\(?\d[\d ().-]{5,}\d"),
+        (
+            ExtractKind::Jwt,
+            r"\beyJ[A-Za-z0-9_-]+\.[A-Za-z0-9_-]+\.[A-Za-z0-9_-]*",
+        ),
+    ]
+    .into_iter()
+    .map(|(kind, pattern)| (kind, Regex::new(pattern).unwrap()))
+    .collect()
+});
+
+// logs are full of timestamps that would otherwise pass as phone numbers
+static DATE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\d{4}-\d{2}-\d{2}").unwrap());
+
+/// the ipv6 pattern allows empty groups, so `foo::bar` or `std::sync` hold candidates that
+/// only look like addresses once cut out of the surrounding word
+fn standalone(text: &str, start: usize, end: usize) -> bool {
+    let part_of_word = |c: char| c.is_alphanumeric() || c == '_' || c == ':';
+    !text[..start].chars().next_back().is_some_and(part_of_word)
+        && !text[end..].chars().next().is_some_and(part_of_word)
+}
+
+fn confirm(kind: ExtractKind, candidate: &str, region: &str) -> bool {
+    match kind {
+        ExtractKind::Email => is_valid_email(candidate),
+        ExtractKind::PhoneNumber => {
+            !DATE.is_match(candidate) && normalize_phone_number(candidate, region).is_some()
+        }
+        ExtractKind::Url => is_valid_url(candidate),
+        ExtractKind::Ip => is_valid_ip(candidate),
+        ExtractKind::Uuid => is_valid_uuid(candidate),
+        ExtractKind::Jwt => jwt::is_jwt(candidate),
+    }
+}
+
+/// returns the matches of the requested kinds in the order they appear, duplicates and
+/// matches overlapping an earlier kind (eg the digits of an ip as a phone number) are skipped
+pub fn extract(text: &str, kinds: &[ExtractKind]) -> Vec<(ExtractKind, String)> {
+    let region = settings::get().phone_region;
+    let mut found: Vec<(usize, usize, ExtractKind)> = vec![];
+    for (kind, regex) in CANDIDATES.iter() {
+        for candidate in regex.find_iter(text) {
+            // urls often end a sentence
+            let value = candidate
+                .as_str()
+                .trim_end_matches(['.', ',', ';', ':', '!', '?', ')']);
+            let (start, end) = (candidate.start(), candidate.start() + value.len());
+            let overlaps = found.iter().any(|(s, e, _)| start < *e && *s < end);
+            if *kind == ExtractKind::Ip && !standalone(text, start, end) {
+                continue;
+            }
+            if !overlaps && confirm(*kind, value, &region) {
+                found.push((start, end, *kind));
+            }
+        }
+    }
+    found.sort_by_key(|(start, _, _)| *start);
+    let mut matches: Vec<(ExtractKind, String)> = vec![];
+    for (start, end, kind) in found {
+        let value = text[start..end].to_string();
+        if kinds.contains(&kind) && !matches.iter().any(|(_, v)| *v == value) {
+            matches.push((kind, value));
+        }
+    }
+    matches
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_extract() {
+        let text = "2024-10-01 12:30:45 ERROR user=jane@example.com ip=10.0.0.12 \
+            req=67e55044-10b1-426f-9247-bb680e5fe0c8 see https://example.com/docs. \
+            call +1 (415) 555-2671 or jane@example.com, v6 fe80::1";
+        let all = [
+            ExtractKind::Email,
+            ExtractKind::PhoneNumber,
+            ExtractKind::Url,
+            ExtractKind::Ip,
+            ExtractKind::Uuid,
+            ExtractKind::Jwt,
+        ];
+        assert_eq!(
+            extract(text, &all),
+            vec![
+                (ExtractKind::Email, "jane@example.com".to_string()),
+                (ExtractKind::Ip, "10.0.0.12".to_string()),
+                (
+                    ExtractKind::Uuid,
+                    "67e55044-10b1-426f-9247-bb680e5fe0c8".to_string()
+                ),
+                (ExtractKind::Url, "https://example.com/docs".to_string()),
+                (ExtractKind::PhoneNumber, "+1 (415) 555-2671".to_string()),
+                (ExtractKind::Ip, "fe80::1".to_string()),
+            ]
+        );
+        assert_eq!(
+            extract(text, &[ExtractKind::Uuid]),
+            vec![(
+                ExtractKind::Uuid,
+                "67e55044-10b1-426f-9247-bb680e5fe0c8".to_string()
+            )]
+        );
+        for code in [
+            "error in foo::bar",
+            "use std::sync::Arc;",
+            "a::b::c",
+            "at 12:30:45",
+        ] {
+            assert!(extract(code, &[ExtractKind::Ip]).is_empty(), "{}", code);
+        }
+        assert_eq!(
+            extract("bind [::1]:8080", &[ExtractKind::Ip]),
+            vec![(ExtractKind::Ip, "::1".to_string())]
+        );
+    }
+}
@@ -0,0 +1,761 @@
+use crate::data::ProgrammingLanguage;
+use crate::json;
+use quick_xml::events::Event;
+use quick_xml::{Reader, Writer};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+pub struct FormatOptions {
+    pub indent_width: u8,
+    pub use_tabs: bool,
+    /// overrides the language of the entry, eg for yaml that was stored as a plain string
+    pub lang: Option<ProgrammingLanguage>,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            indent_width: 2,
+            use_tabs: false,
+            lang: None,
+        }
+    }
+}
+
+impl FormatOptions {
+    fn indent(&self) -> String {
+        if self.use_tabs {
+            "\t".into()
+        } else {
+            " ".repeat(self.indent_width as usize)
+        }
+    }
+    fn spaces(&self) -> String {
+        " ".repeat(self.indent_width.max(1) as usize)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+#[serde(tag = "kind", content = "content")]
+pub enum FormatError {
+    NotFound,
+    Unsupported(ProgrammingLanguage),
+    Parse {
+        lang: ProgrammingLanguage,
+        message: String,
+    },
+    /// formatting would drop the comments of the entry
+    HasComments(ProgrammingLanguage),
+}
+
+fn parse_error(lang: ProgrammingLanguage, message: impl ToString) -> FormatError {
+    FormatError::Parse {
+        lang,
+        message: message.to_string(),
+    }
+}
+
+pub fn format(
+    text: &str,
+    lang: ProgrammingLanguage,
+    options: &FormatOptions,
+) -> Result<String, FormatError> {
+    match lang {
+        ProgrammingLanguage::JSON => {
+            json::format(text, &options.indent()).ok_or_else(|| parse_error(lang, "invalid json"))
+        }
+        // yaml does not allow tabs for indentation
+        ProgrammingLanguage::YAML => format_yaml(text, &options.spaces()),
+        ProgrammingLanguage::TOML => format_toml(text, &options.indent()),
+        ProgrammingLanguage::XML => format_xml(text, options),
+        ProgrammingLanguage::HTML => format_html(text, &options.indent()),
+        ProgrammingLanguage::CSS => format_css(text, &options.indent()),
+        ProgrammingLanguage::SQL => Ok(sqlformat::format(
+            text,
+            &sqlformat::QueryParams::None,
+            sqlformat::FormatOptions {
+                indent: if options.use_tabs {
+                    sqlformat::Indent::Tabs
+                } else {
+                    sqlformat::Indent::Spaces(options.indent_width)
+                },
+                ..Default::default()
+            },
+        )),
+        lang => Err(FormatError::Unsupported(lang)),
+    }
+}
+
+/// serializers indent with a fixed width, this rescales the leading whitespace
+fn reindent(text: &str, width: usize, indent: &str) -> String {
+    if indent == " ".repeat(width) {
+        return text.into();
+    }
+    text.lines()
+        .map(|line| {
+            let spaces = line.len() - line.trim_start_matches(' ').len();
+            format!(
+                "{}{}{}",
+                indent.repeat(spaces / width),
+                " ".repeat(spaces % width),
+                &line[spaces..]
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// true when a `#` outside of a quoted string starts a comment, as in yaml and toml
+fn has_comments(text: &str) -> bool {
+    text.lines().any(|line| {
+        let mut quote = None;
+        let mut previous = ' ';
+        for c in line.chars() {
+            match (quote, c) {
+                (None, '#') if previous.is_whitespace() => return true,
+                // quotes only open a string at the start of a value, not in `don't`
+                (None, '"' | '\'') if previous.is_whitespace() || "[{,:=".contains(previous) => {
+                    quote = Some(c)
+                }
+                (Some('"'), '"') if previous != '\\' => quote = None,
+                (Some('\''), '\'') => quote = None,
+                _ => {}
+            }
+            previous = c;
+        }
+        false
+    })
+}
+
+/// yaml and toml are formatted by round tripping through a parsed value, which loses comments,
+/// expands yaml anchors and normalizes quoting. entries with comments are refused rather than
+/// rewritten without them
+fn format_yaml(text: &str, indent: &str) -> Result<String, FormatError> {
+    use serde::Deserialize as _;
+    let lang = ProgrammingLanguage::YAML;
+    if has_comments(text) {
+        return Err(FormatError::HasComments(lang));
+    }
+    let mut documents = vec![];
+    for document in serde_yaml::Deserializer::from_str(text) {
+        let value = serde_yaml::Value::deserialize(document).map_err(|e| parse_error(lang, e))?;
+        documents.push(serde_yaml::to_string(&value).map_err(|e| parse_error(lang, e))?);
+    }
+    Ok(reindent(documents.join("---\n").trim_end(), 2, indent))
+}
+
+fn format_toml(text: &str, indent: &str) -> Result<String, FormatError> {
+    let lang = ProgrammingLanguage::TOML;
+    if has_comments(text) {
+        return Err(FormatError::HasComments(lang));
+    }
+    let table = text
+        .parse::<toml::Table>()
+        .map_err(|e| parse_error(lang, e.message()))?;
+    let formatted = toml::to_string_pretty(&table).map_err(|e| parse_error(lang, e))?;
+    Ok(reindent(formatted.trim_end(), 4, indent))
+}
+
+fn format_xml(text: &str, options: &FormatOptions) -> Result<String, FormatError> {
+    let lang = ProgrammingLanguage::XML;
+    let mut reader = Reader::from_str(text);
+    reader.config_mut().trim_text(true);
+    let mut writer = if options.use_tabs {
+        Writer::new_with_indent(Vec::new(), b'\t', 1)
+    } else {
+        Writer::new_with_indent(Vec::new(), b' ', options.indent_width as usize)
+    };
+    let mut depth = 0usize;
+    loop {
+        let event = reader.read_event().map_err(|e| parse_error(lang, e))?;
+        match &event {
+            Event::Eof => break,
+            Event::Start(_) => depth += 1,
+            Event::End(_) => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        writer
+            .write_event(event)
+            .map_err(|e| parse_error(lang, e))?;
+    }
+    if depth != 0 {
+        return Err(parse_error(lang, "unclosed tag at end of input"));
+    }
+    String::from_utf8(writer.into_inner()).map_err(|e| parse_error(lang, e))
+}
+
+enum HtmlToken<'a> {
+    Open(&'a str),
+    Close(&'a str),
+    SelfClosing(&'a str),
+    Text(&'a str),
+}
+
+const VOID_ELEMENTS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+const RAW_ELEMENTS: [&str; 4] = ["script", "style", "pre", "textarea"];
+
+fn tag_name(tag: &str) -> String {
+    tag.trim_start_matches(['<', '/'])
+        .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .next()
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+/// finds the `>` closing a tag starting at `start`, skipping quoted attribute values
+fn tag_end(text: &str, start: usize) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in text[start..].char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') => return Some(start + i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn tokenize_html(text: &str) -> Result<Vec<HtmlToken<'_>>, FormatError> {
+    let lang = ProgrammingLanguage::HTML;
+    let mut tokens = vec![];
+    let mut pos = 0;
+    while pos < text.len() {
+        let Some(offset) = text[pos..].find('<') else {
+            tokens.push(HtmlToken::Text(&text[pos..]));
+            break;
+        };
+        if offset > 0 {
+            tokens.push(HtmlToken::Text(&text[pos..pos + offset]));
+        }
+        let start = pos + offset;
+        let end = if text[start..].starts_with("<!--") {
+            text[start..]
+                .find("-->")
+                .map(|i| start + i + 2)
+                .ok_or_else(|| parse_error(lang, "unterminated comment"))?
+        } else {
+            tag_end(text, start).ok_or_else(|| parse_error(lang, "unterminated tag"))?
+        };
+        let tag = &text[start..=end];
+        pos = end + 1;
+        let name = tag_name(tag);
+        if tag.starts_with("</") {
+            tokens.push(HtmlToken::Close(tag));
+        } else if tag.starts_with("<!")
+            || tag.starts_with("<?")
+            || tag.ends_with("/>")
+            || VOID_ELEMENTS.contains(&name.as_str())
+        {
+            tokens.push(HtmlToken::SelfClosing(tag));
+        } else if RAW_ELEMENTS.contains(&name.as_str()) {
+            // content of raw elements is kept verbatim
+            let close = format!("</{}", name);
+            let content_end = text[pos..]
+                .to_ascii_lowercase()
+                .find(&close)
+                .map(|i| pos + i)
+                .ok_or_else(|| parse_error(lang, format!("unclosed <{}>", name)))?;
+            let close_end =
+                tag_end(text, content_end).ok_or_else(|| parse_error(lang, "unterminated tag"))?;
+            tokens.push(HtmlToken::SelfClosing(&text[start..=close_end]));
+            pos = close_end + 1;
+        } else {
+            tokens.push(HtmlToken::Open(tag));
+        }
+    }
+    Ok(tokens)
+}
+
+fn format_html(text: &str, indent: &str) -> Result<String, FormatError> {
+    let tokens = tokenize_html(text)?;
+    let mut lines = vec![];
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < tokens.len() {
+        let pad = indent.repeat(depth);
+        match &tokens[i] {
+            HtmlToken::Text(text) => {
+                let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                if !text.is_empty() {
+                    lines.push(format!("{}{}", pad, text));
+                }
+            }
+            HtmlToken::SelfClosing(tag) => lines.push(format!("{}{}", pad, tag)),
+            HtmlToken::Close(tag) => {
+                depth = depth.saturating_sub(1);
+                lines.push(format!("{}{}", indent.repeat(depth), tag));
+            }
+            HtmlToken::Open(tag) => {
+                // keep `<p>short text</p>` on a single line
+                if let (Some(HtmlToken::Text(text)), Some(HtmlToken::Close(close))) =
+                    (tokens.get(i + 1), tokens.get(i + 2))
+                {
+                    if tag_name(tag) == tag_name(close) && !text.contains('\n') {
+                        lines.push(format!("{}{}{}{}", pad, tag, text.trim(), close));
+                        i += 3;
+                        continue;
+                    }
+                }
+                lines.push(format!("{}{}", pad, tag));
+                depth += 1;
+            }
+        }
+        i += 1;
+    }
+    Ok(lines.join("\n"))
+}
+
+fn format_css(text: &str, indent: &str) -> Result<String, FormatError> {
+    let lang = ProgrammingLanguage::CSS;
+    let mut lines: Vec<String> = vec![];
+    let mut line = String::new();
+    let mut depth = 0usize;
+    let mut chars = text.chars().peekable();
+
+    fn flush(lines: &mut Vec<String>, line: &mut String, indent: &str, depth: usize) {
+        let mut trimmed = line.trim().to_string();
+        // declarations get a single space after the property name
+        if let (true, Some((property, value))) = (depth > 0, trimmed.split_once(':')) {
+            trimmed = format!("{}: {}", property.trim_end(), value.trim_start());
+        }
+        if !trimmed.is_empty() {
+            lines.push(format!("{}{}", indent.repeat(depth), trimmed));
+        }
+        line.clear();
+    }
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' | '\'' => {
+                line.push(c);
+                loop {
+                    match chars.next() {
+                        Some('\\') => {
+                            line.push('\\');
+                            line.extend(chars.next());
+                        }
+                        Some(next) => {
+                            line.push(next);
+                            if next == c {
+                                break;
+                            }
+                        }
+                        None => return Err(parse_error(lang, "unterminated string")),
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut comment = String::from("/*");
+                loop {
+                    match chars.next() {
+                        Some('*') if chars.peek() == Some(&'/') => {
+                            chars.next();
+                            comment.push_str("*/");
+                            break;
+                        }
+                        Some(next) => comment.push(next),
+                        None => return Err(parse_error(lang, "unterminated comment")),
+                    }
+                }
+                flush(&mut lines, &mut line, indent, depth);
+                lines.push(format!("{}{}", indent.repeat(depth), comment));
+            }
+            '{' => {
+                let selector = line.trim().to_string();
+                line.clear();
+                lines.push(format!("{}{} {{", indent.repeat(depth), selector));
+                depth += 1;
+            }
+            '}' => {
+                flush(&mut lines, &mut line, indent, depth);
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or_else(|| parse_error(lang, "unexpected `}`"))?;
+                lines.push(format!("{}}}", indent.repeat(depth)));
+            }
+            ';' => {
+                line.push(';');
+                flush(&mut lines, &mut line, indent, depth);
+            }
+            c if c.is_whitespace() => {
+                if !line.is_empty() && !line.ends_with(' ') {
+                    line.push(' ');
+                }
+            }
+            c => line.push(c),
+        }
+    }
+    if depth != 0 {
+        return Err(parse_error(lang, "missing `}`"));
+    }
+    flush(&mut lines, &mut line, indent, depth);
+    Ok(lines.join("\n"))
+}
+
+// ----------------------- MINIFY --------------------- //
+
+pub fn minify(text: &str, lang: ProgrammingLanguage) -> Result<String, FormatError> {
+    match lang {
+        ProgrammingLanguage::JSON => json::parse(text)
+            .and_then(|value| serde_json::to_string(&value).ok())
+            .ok_or_else(|| parse_error(lang, "invalid json")),
+        ProgrammingLanguage::XML => minify_xml(text),
+        ProgrammingLanguage::HTML => minify_html(text),
+        ProgrammingLanguage::CSS => minify_css(text),
+        ProgrammingLanguage::JavaScript => minify_js(text),
+        lang => Err(FormatError::Unsupported(lang)),
+    }
+}
+
+fn minify_xml(text: &str) -> Result<String, FormatError> {
+    let lang = ProgrammingLanguage::XML;
+    let mut reader = Reader::from_str(text);
+    reader.config_mut().trim_text(true);
+    let mut writer = Writer::new(Vec::new());
+    let mut depth = 0usize;
+    loop {
+        let event = reader.read_event().map_err(|e| parse_error(lang, e))?;
+        match &event {
+            Event::Eof => break,
+            Event::Comment(_) => continue,
+            Event::Start(_) => depth += 1,
+            Event::End(_) => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        writer
+            .write_event(event)
+            .map_err(|e| parse_error(lang, e))?;
+    }
+    if depth != 0 {
+        return Err(parse_error(lang, "unclosed tag at end of input"));
+    }
+    String::from_utf8(writer.into_inner()).map_err(|e| parse_error(lang, e))
+}
+
+fn minify_html(text: &str) -> Result<String, FormatError> {
+    let mut out = String::new();
+    for token in tokenize_html(text)? {
+        match token {
+            HtmlToken::Text(text) => {
+                // whitespace between inline elements is significant, collapse it to one space
+                let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                if collapsed.is_empty() {
+                    continue;
+                }
+                if text.starts_with(char::is_whitespace) && !out.ends_with('>') {
+                    out.push(' ');
+                }
+                out.push_str(&collapsed);
+                if text.ends_with(char::is_whitespace) {
+                    out.push(' ');
+                }
+            }
+            HtmlToken::SelfClosing(tag) if tag.starts_with("<!--") => {}
+            HtmlToken::Open(tag) | HtmlToken::Close(tag) | HtmlToken::SelfClosing(tag) => {
+                out.push_str(tag)
+            }
+        }
+    }
+    Ok(out.trim().to_string())
+}
+
+/// skips a quoted string starting at `chars[start]`, returns the index after the closing quote
+fn skip_string(chars: &[char], start: usize) -> Option<usize> {
+    let quote = chars[start];
+    let mut i = start + 1;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 2,
+            c if c == quote => return Some(i + 1),
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+fn minify_css(text: &str) -> Result<String, FormatError> {
+    let lang = ProgrammingLanguage::CSS;
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+    let mut pending_space = false;
+    while i < chars.len() {
+        match chars[i] {
+            '"' | '\'' => {
+                let end = skip_string(&chars, i)
+                    .ok_or_else(|| parse_error(lang, "unterminated string"))?;
+                if pending_space && !out.ends_with(['{', '}', ';', ',', '>', ':']) {
+                    out.push(' ');
+                }
+                pending_space = false;
+                out.extend(&chars[i..end]);
+                i = end;
+                continue;
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                let end = (i + 2..chars.len().saturating_sub(1))
+                    .find(|&j| chars[j] == '*' && chars[j + 1] == '/')
+                    .ok_or_else(|| parse_error(lang, "unterminated comment"))?;
+                i = end + 2;
+                continue;
+            }
+            c if c.is_whitespace() => pending_space = !out.is_empty(),
+            c @ ('{' | '}' | ';' | ',' | '>' | ':') => {
+                if c == '}' && out.ends_with(';') {
+                    out.pop();
+                }
+                // keep the space in selectors like `a :hover`
+                if c == ':' && pending_space && !out.ends_with(['{', ';']) {
+                    out.push(' ');
+                }
+                out.push(c);
+                pending_space = false;
+            }
+            c => {
+                if pending_space && !out.ends_with(['{', '}', ';', ',', '>', ':']) {
+                    out.push(' ');
+                }
+                pending_space = false;
+                out.push(c);
+            }
+        }
+        i += 1;
+    }
+    Ok(out)
+}
+
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+/// strips comments and whitespace from javascript without renaming anything. newlines are kept
+/// where removing them could change automatic semicolon insertion
+fn minify_js(text: &str) -> Result<String, FormatError> {
+    let lang = ProgrammingLanguage::JavaScript;
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+    let mut pending: Option<char> = None;
+
+    fn push(out: &mut String, pending: &mut Option<char>, next: char) {
+        if let (Some(ws), Some(prev)) = (pending.take(), out.chars().last()) {
+            let joins_token = (is_word(prev) && is_word(next))
+                || (prev == next && "+-/".contains(prev))
+                || (prev == '/' && next == '*');
+            let keeps_newline = ws == '\n'
+                && !"{(,;[=:?&|!+-*/%<>^~".contains(prev)
+                && !"})],;.?:=&|".contains(next);
+            if keeps_newline {
+                out.push('\n');
+            } else if joins_token {
+                out.push(' ');
+            }
+        }
+        out.push(next);
+    }
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '"' | '\'' | '`' => {
+                let end = skip_string(&chars, i)
+                    .ok_or_else(|| parse_error(lang, "unterminated string"))?;
+                push(&mut out, &mut pending, c);
+                out.extend(&chars[i + 1..end]);
+                i = end;
+                continue;
+            }
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                let end = (i + 2..chars.len().saturating_sub(1))
+                    .find(|&j| chars[j] == '*' && chars[j + 1] == '/')
+                    .ok_or_else(|| parse_error(lang, "unterminated comment"))?;
+                pending = pending.or(Some(' '));
+                i = end + 2;
+                continue;
+            }
+            '/' if {
+                let prev = out.trim_end().chars().last();
+                let last_word: String = out
+                    .chars()
+                    .rev()
+                    .take_while(|c| is_word(*c))
+                    .collect::<String>()
+                    .chars()
+                    .rev()
+                    .collect();
+                prev.is_none_or(|p| "(,=:[!&|?{};+-*%<>~^".contains(p))
+                    || ["return", "typeof", "case", "in", "of"].contains(&last_word.as_str())
+            } =>
+            {
+                // regex literal, copied verbatim
+                let mut j = i + 1;
+                let mut class = false;
+                while j < chars.len() {
+                    match chars[j] {
+                        '\\' => j += 1,
+                        '[' => class = true,
+                        ']' => class = false,
+                        '/' if !class => break,
+                        '\n' => return Err(parse_error(lang, "unterminated regex")),
+                        _ => {}
+                    }
+                    j += 1;
+                }
+                push(&mut out, &mut pending, '/');
+                out.extend(&chars[i + 1..(j + 1).min(chars.len())]);
+                i = j + 1;
+                continue;
+            }
+            c if c.is_whitespace() => {
+                if !out.is_empty() {
+                    pending = match (pending, c) {
+                        (_, '\n') | (Some('\n'), _) => Some('\n'),
+                        _ => Some(' '),
+                    };
+                }
+            }
+            c => push(&mut out, &mut pending, c),
+        }
+        i += 1;
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn fmt(text: &str, lang: ProgrammingLanguage) -> Result<String, FormatError> {
+        format(text, lang, &FormatOptions::default())
+    }
+
+    #[test]
+    fn test_yaml_toml() {
+        assert_eq!(
+            fmt("a:   1\nb: [x, y]", ProgrammingLanguage::YAML).unwrap(),
+            "a: 1\nb:\n- x\n- y"
+        );
+        assert!(matches!(
+            fmt("a: [", ProgrammingLanguage::YAML),
+            Err(FormatError::Parse { .. })
+        ));
+        assert_eq!(
+            fmt("a=1\n[b]\nc =   \"d\"", ProgrammingLanguage::TOML).unwrap(),
+            "a = 1\n\n[b]\nc = \"d\""
+        );
+        assert_eq!(
+            fmt("# deploy\nkind: Pod # inline", ProgrammingLanguage::YAML),
+            Err(FormatError::HasComments(ProgrammingLanguage::YAML))
+        );
+        assert_eq!(
+            fmt("a = 1 # one", ProgrammingLanguage::TOML),
+            Err(FormatError::HasComments(ProgrammingLanguage::TOML))
+        );
+        assert_eq!(
+            fmt("a: don't # me", ProgrammingLanguage::YAML),
+            Err(FormatError::HasComments(ProgrammingLanguage::YAML))
+        );
+        assert_eq!(
+            fmt("a = 'x' # one", ProgrammingLanguage::TOML),
+            Err(FormatError::HasComments(ProgrammingLanguage::TOML))
+        );
+        assert_eq!(
+            fmt("a: \"#1\"\nb: 'x #2'\nc: d#3", ProgrammingLanguage::YAML).unwrap(),
+            "a: '#1'\nb: 'x #2'\nc: d#3"
+        );
+    }
+
+    #[test]
+    fn test_xml_html() {
+        assert_eq!(
+            fmt("<a><b>1</b><c/></a>", ProgrammingLanguage::XML).unwrap(),
+            "<a>\n  <b>1</b>\n  <c/>\n</a>"
+        );
+        assert!(fmt("<a><b></a>", ProgrammingLanguage::XML).is_err());
+        assert_eq!(
+            fmt(
+                "<div><p>hi</p><br><script>if (a<b) {}</script></div>",
+                ProgrammingLanguage::HTML
+            )
+            .unwrap(),
+            "<div>\n  <p>hi</p>\n  <br>\n  <script>if (a<b) {}</script>\n</div>"
+        );
+    }
+
+    #[test]
+    fn test_css() {
+        assert_eq!(
+            fmt(
+                "a,b{color:red;background:url('x;y')}/* c */@media (x){p{margin:0}}",
+                ProgrammingLanguage::CSS
+            )
+            .unwrap(),
+            "a,b {\n  color: red;\n  background: url('x;y')\n}\n/* c */\n@media (x) {\n  p {\n    margin: 0\n  }\n}"
+        );
+        assert!(fmt("a { color: red", ProgrammingLanguage::CSS).is_err());
+    }
+
+    #[test]
+    fn test_unsupported() {
+        assert_eq!(
+            fmt("fn main() {}", ProgrammingLanguage::Rust),
+            Err(FormatError::Unsupported(ProgrammingLanguage::Rust))
+        );
+    }
+
+    #[test]
+    fn test_minify() {
+        assert_eq!(
+            minify(
+                "{\n  \"a\": [1, 2],\n  // c\n  b: null\n}",
+                ProgrammingLanguage::JSON
+            )
+            .unwrap(),
+            r#"{"a":[1,2],"b":null}"#
+        );
+        assert_eq!(
+            minify(
+                "<a>\n  <!-- c -->\n  <b> 1 </b>\n</a>",
+                ProgrammingLanguage::XML
+            )
+            .unwrap(),
+            "<a><b>1</b></a>"
+        );
+        assert_eq!(
+            minify(
+                "<div>\n  <!-- c -->\n  <p>hello <b>world</b></p>\n</div>",
+                ProgrammingLanguage::HTML
+            )
+            .unwrap(),
+            "<div><p>hello <b>world</b></p></div>"
+        );
+        assert_eq!(
+            minify(
+                "/* c */\na :hover,\nb > i {\n  color: red;\n  content: ' ; ';\n}",
+                ProgrammingLanguage::CSS
+            )
+            .unwrap(),
+            "a :hover,b>i{color:red;content:' ; '}"
+        );
+        assert_eq!(
+            minify(
+                "// c\nconst a = 1 + +b; /* x */\nlet s = \"a  b\"\nreturn /a b/g.test(s)\n",
+                ProgrammingLanguage::JavaScript
+            )
+            .unwrap(),
+            "const a=1+ +b;let s=\"a  b\"\nreturn/a b/g.test(s)"
+        );
+    }
+}
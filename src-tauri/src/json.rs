@@ -0,0 +1,125 @@
+use serde::Serialize as _;
+use serde_json::Value;
+use serde_json_path::JsonPath;
+
+/// parses strict json first and falls back to json5, which also covers jsonc
+/// (comments, trailing commas). scalars like `1` or `"a"` are not treated as json
+pub fn parse(value: &str) -> Option<Value> {
+    let trimmed = value.trim_start();
+    if !trimmed.starts_with(['{', '[', '/']) {
+        return None;
+    }
+    let parsed = serde_json::from_str::<Value>(value)
+        .ok()
+        .or_else(|| json5::from_str::<Value>(value).ok())?;
+    match parsed {
+        Value::Object(_) | Value::Array(_) => Some(parsed),
+        _ => None,
+    }
+}
+
+/// strict json is re-indented as is, json5 input is normalized to json
+pub fn format(value: &str, indent: &str) -> Option<String> {
+    if serde_json::from_str::<serde::de::IgnoredAny>(value).is_ok() {
+        return Some(jsonformat::format(
+            value,
+            jsonformat::Indentation::Custom(indent),
+        ));
+    }
+    let mut buf = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+    let mut serializer = serde_json::Serializer::with_formatter(&mut buf, formatter);
+    parse(value)?.serialize(&mut serializer).ok()?;
+    String::from_utf8(buf).ok()
+}
+
+/// evaluates a JSONPath expression, jq style paths like `.items[0].name` are
+/// accepted as well. a single match is returned as is, several as an array
+pub fn query(value: &Value, path: &str) -> Result<Value, String> {
+    let path = path.trim();
+    let path = match path {
+        "" | "." => "$".to_string(),
+        _ if path.starts_with(".[") => format!("${}", &path[1..]),
+        _ if path.starts_with('.') => format!("${}", path),
+        _ => path.to_string(),
+    };
+    let path = JsonPath::parse(&path).map_err(|e| e.to_string())?;
+    let nodes = path.query(value);
+    match nodes.len() {
+        0 => Err("path did not match anything".into()),
+        1 => Ok(nodes.first().cloned().unwrap_or_default()),
+        _ => Ok(Value::Array(nodes.all().into_iter().cloned().collect())),
+    }
+}
+
+/// merges `other` into `value`, objects are merged key by key, arrays are concatenated and
+/// anything else is replaced by `other`
+pub fn merge(value: &mut Value, other: Value) {
+    match (value, other) {
+        (Value::Object(value), Value::Object(other)) => {
+            for (key, other) in other {
+                match value.get_mut(&key) {
+                    Some(existing) => merge(existing, other),
+                    None => {
+                        value.insert(key, other);
+                    }
+                }
+            }
+        }
+        (Value::Array(value), Value::Array(other)) => value.extend(other),
+        (value, other) => *value = other,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert!(parse("{\"a\": 1}").is_some());
+        assert!(parse("[1,2,3]").is_some());
+        assert!(parse("  [{\"id\": 1}, {\"id\": 2}]").is_some());
+        assert!(parse("{\n  // comment\n  a: 1,\n}").is_some());
+        assert!(parse("/* header */ [1, 2,]").is_some());
+        assert!(parse("1").is_none());
+        assert!(parse("\"string\"").is_none());
+        assert!(parse("[not json").is_none());
+        assert!(parse("// just a comment").is_none());
+    }
+
+    #[test]
+    fn test_format_json5() {
+        assert_eq!(
+            format("{b: 1, // c\n a: [1,],}", "  ").unwrap(),
+            "{\n  \"b\": 1,\n  \"a\": [\n    1\n  ]\n}"
+        );
+    }
+
+    #[test]
+    fn test_query() {
+        let value = parse(r#"{"items": [{"name": "a"}, {"name": "b"}]}"#).unwrap();
+        assert_eq!(query(&value, ".items[0].name").unwrap(), "a");
+        assert_eq!(query(&value, "$.items[1].name").unwrap(), "b");
+        assert_eq!(
+            query(&value, "$..name").unwrap(),
+            serde_json::json!(["a", "b"])
+        );
+        assert_eq!(query(&value, ".").unwrap(), value);
+        assert!(query(&value, ".missing").is_err());
+        assert!(query(&value, "$[").is_err());
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut value = serde_json::json!({"a": {"b": 1, "c": [1]}, "d": "x"});
+        merge(
+            &mut value,
+            serde_json::json!({"a": {"c": [2], "e": null}, "d": "y"}),
+        );
+        assert_eq!(
+            value,
+            serde_json::json!({"a": {"b": 1, "c": [1, 2], "e": null}, "d": "y"})
+        );
+    }
+}
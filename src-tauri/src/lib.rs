@@ -1,24 +1,48 @@
 use arboard::Clipboard;
 use commands::{
-    delete_by_id, format_json, fuzzy_search, get_all_data, get_all_id, get_by_id,
-    get_pool_clipboard_state, set_pool_clipboard_state, update_data_by_id,
+    cancel_search, convert_entry, copy_snippet, create_snippet, decode_entry, decode_jwt,
+    delete_by_id, delete_snippet, diff_entries, encode_entry, extract_from_entry, format_entry,
+    format_json, fuzzy_search, get_all_data, get_all_id, get_by_id, get_pool_clipboard_state,
+    get_settings, join_entries, json_query, list_collections, list_detectors, list_revisions,
+    list_snippet_folders, list_snippets, minify_entry, regex_search, revert_revision, search,
+    set_pinned, set_pool_clipboard_state, set_settings, similar_search, snippet_from_entry,
+    snippet_inputs, split_entry, stream_search, tag_entry, transform_entry, untag_entry,
+    update_data_by_id, update_snippet,
 };
 use common::EventNames;
 use popup::{popup_show, PopupWindow};
 use specta::TypeCollection;
 use specta_typescript::{BigIntExportBehavior, Typescript};
 use std::{
-    sync::{Arc, RwLock},
+    sync::{atomic::AtomicU64, Arc, RwLock},
     thread,
 };
 use tauri::{Emitter as _, Manager as _};
 use tauri_specta::{collect_commands, Builder};
+mod codec;
 mod commands;
 mod common;
+mod convert;
 mod data;
+mod detector;
+mod diff;
 mod double_linked_list_multi_thread;
+mod extract;
+mod formatter;
+mod json;
+mod jwt;
 mod lru_multi_thread;
+mod persist;
 mod popup;
+mod query;
+mod ranking;
+mod regex_search;
+mod search_index;
+mod settings;
+mod similarity;
+mod snippets;
+mod template;
+mod transform;
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
@@ -29,14 +53,23 @@ fn health(slug: &str) -> String {
 //-------------------------- STATE -------------------------------------
 struct ClipboardHistory {
     pub data: RwLock<lru_multi_thread::Lru>,
+    /// last text seen on the system clipboard, text the app copies itself is set here first so
+    /// the poller does not add it to the history
+    pub last_value: RwLock<String>,
 }
 impl ClipboardHistory {
     pub fn new() -> Self {
         Self {
             data: RwLock::new(lru_multi_thread::Lru::new(1000)),
+            last_value: RwLock::new(String::new()),
         }
     }
 }
+/// bumped by every streaming search so a running search notices it was superseded
+#[derive(Default)]
+pub struct SearchState {
+    pub generation: AtomicU64,
+}
 pub struct PoolClipboard {
     pub value: bool,
     pub app_handle: tauri::AppHandle,
@@ -77,7 +110,41 @@ pub fn run() {
             get_pool_clipboard_state,
             set_pool_clipboard_state,
             fuzzy_search,
-            format_json
+            format_json,
+            decode_jwt,
+            decode_entry,
+            encode_entry,
+            get_settings,
+            set_settings,
+            json_query,
+            format_entry,
+            minify_entry,
+            convert_entry,
+            transform_entry,
+            list_revisions,
+            revert_revision,
+            diff_entries,
+            join_entries,
+            split_entry,
+            extract_from_entry,
+            list_detectors,
+            search,
+            set_pinned,
+            regex_search,
+            stream_search,
+            cancel_search,
+            similar_search,
+            tag_entry,
+            untag_entry,
+            list_collections,
+            list_snippets,
+            list_snippet_folders,
+            create_snippet,
+            snippet_from_entry,
+            update_snippet,
+            delete_snippet,
+            copy_snippet,
+            snippet_inputs
         ]);
 
     #[cfg(debug_assertions)] // <- Only export on non-release builds
@@ -102,26 +169,40 @@ pub fn run() {
         .invoke_handler(builder.invoke_handler())
         .setup(move |app| {
             // This is also required if you want to use events
+            // settings first, the history reads them when it is built
+            if let Err(err) = settings::load(app.path().app_data_dir()?.join("settings.json")) {
+                eprintln!("{}, using the default settings", err);
+            }
             let history = Arc::new(ClipboardHistory::new());
             let history_clone = Arc::clone(&history);
             app.manage(history);
             app.manage(popup::PopupWindow::default());
+            app.manage(Arc::new(SearchState::default()));
+            let snippets_path = app.path().app_data_dir()?.join("snippets.json");
+            // a broken store was kept aside as .bak, start empty rather than not at all
+            let snippets =
+                snippets::SnippetStore::load(snippets_path.clone()).unwrap_or_else(|err| {
+                    eprintln!("{}", err);
+                    snippets::SnippetStore::new(snippets_path)
+                });
+            app.manage(Arc::new(RwLock::new(snippets)));
             let app_handle = app.handle();
             let pool_clipboard = Arc::new(RwLock::new(PoolClipboard::new(app_handle.clone())));
             let pool_clipboard_clone = Arc::clone(&pool_clipboard);
             popup_show(app.app_handle().clone());
             thread::spawn(move || {
                 let mut clipboard = Clipboard::new().expect("Failed to access Clipboard");
-                let mut last_value = String::new();
                 loop {
                     let pool_clipboard = pool_clipboard_clone.read().unwrap();
                     if (*pool_clipboard).get() {
                         drop(pool_clipboard);
+                        // held while reading so a copy by the app can't slip in between
+                        let mut last_value = history_clone.last_value.write().unwrap();
                         if let Ok(current) = clipboard.get_text() {
-                            if current != last_value {
+                            if current != *last_value {
                                 let mut lru = history_clone.data.write().unwrap();
                                 lru.insert(current.clone());
-                                last_value = current;
+                                *last_value = current;
                             }
                         }
                     } else {
@@ -1,31 +1,68 @@
 use crate::data::*;
 use crate::double_linked_list_multi_thread::{DoubleLinkedList, Node};
+use crate::search_index::SearchIndex;
+use crate::settings;
+use serde::{Deserialize, Serialize};
+use specta::Type;
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock, Weak};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// bookkeeping kept next to each entry, keyed by the same id
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Type)]
+pub struct EntryMeta {
+    /// unix seconds
+    pub first_copied: u64,
+    /// unix seconds
+    pub last_copied: u64,
+    pub copy_count: u32,
+    pub pinned: bool,
+    pub tags: Vec<String>,
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
 
 #[derive(Debug)]
 pub struct Lru {
     list: DoubleLinkedList<Data>,
     hash: HashMap<String, Weak<RwLock<Node<Data>>>>,
     size: usize,
+    index: SearchIndex,
+    meta: HashMap<String, EntryMeta>,
 }
 
 impl Lru {
     pub fn new(size: usize) -> Self {
+        let mut index = SearchIndex::new();
+        index.set_similarity(settings::get().similarity_search);
         Lru {
             list: DoubleLinkedList::new(),
             hash: HashMap::new(),
             size,
+            index,
+            meta: HashMap::new(),
         }
     }
 
-    pub fn pop(&mut self) -> Option<Data> {
-        if let Some(data) = self.list.pop_back() {
-            self.hash.remove(&data.hash());
-            Some(data)
-        } else {
-            None
+    /// drops the least recently used entry, skipping tagged ones when `protect_tagged` is set.
+    /// returns false when every entry is protected
+    fn evict(&mut self, protect_tagged: bool) -> bool {
+        let mut node = self.list.peak_back();
+        while let Some(current) = node {
+            let id = current.read().unwrap().val().hash();
+            let protected =
+                protect_tagged && self.meta.get(&id).is_some_and(|meta| !meta.tags.is_empty());
+            if !protected {
+                return self.delete(&id).is_ok();
+            }
+            node = current.read().unwrap().prev();
         }
+        false
     }
     pub fn get(&self, id: &str) -> Option<Data> {
         let upgraded = self.hash.get(id).and_then(|weak| weak.upgrade());
@@ -47,32 +84,122 @@ impl Lru {
             None
         }
     }
-    pub fn insert(&mut self, data: String) {
-        let data: Data = Data::from(data);
+    pub fn insert(&mut self, data: String) -> String {
+        self.insert_data(Data::from(data))
+    }
+    /// inserts an already classified entry, returns its id
+    pub fn insert_data(&mut self, data: Data) -> String {
+        self.insert_protecting(data, settings::get().protect_tagged)
+    }
+    fn insert_protecting(&mut self, data: Data, protect_tagged: bool) -> String {
         let hash = data.hash();
+        let time = now();
+        let meta = self.meta.entry(hash.clone()).or_insert_with(|| EntryMeta {
+            first_copied: time,
+            ..Default::default()
+        });
+        meta.last_copied = time;
+        meta.copy_count += 1;
         match self.hash.get_mut(&hash).map_or(None, |e| e.upgrade()) {
             Some(node) => {
                 // let node = node.lock().unwrap();
                 self.list.delete(node.clone());
                 self.list.push_front_rc(node);
+                self.index.touch(&hash);
             }
             None => {
+                self.index.insert(&hash, &data.val());
                 self.list.push_front(data);
-                self.hash
-                    .insert(hash, Arc::downgrade(&self.list.peak_front().unwrap()));
+                self.hash.insert(
+                    hash.clone(),
+                    Arc::downgrade(&self.list.peak_front().unwrap()),
+                );
             }
         }
-        while self.len() > self.size {
-            self.pop();
-        }
+        while self.len() > self.size && self.evict(protect_tagged) {}
+        hash
     }
     pub fn list(&self) -> &DoubleLinkedList<Data> {
         &self.list
     }
+    pub fn index(&self) -> &SearchIndex {
+        &self.index
+    }
+    pub fn set_similarity(&mut self, enabled: bool) {
+        self.index.set_similarity(enabled);
+    }
+    pub fn meta(&self, id: &str) -> Option<&EntryMeta> {
+        self.meta.get(id)
+    }
+    pub fn meta_mut(&mut self, id: &str) -> Option<&mut EntryMeta> {
+        self.meta.get_mut(id)
+    }
+    /// tags are matched case insensitively, the first spelling is kept
+    pub fn tag(&mut self, id: &str, tag: &str) -> Option<()> {
+        let tag = tag.trim();
+        let meta = self.meta.get_mut(id).filter(|_| !tag.is_empty())?;
+        if !meta.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            meta.tags.push(tag.to_string());
+        }
+        Some(())
+    }
+    pub fn untag(&mut self, id: &str, tag: &str) -> Option<()> {
+        let meta = self.meta.get_mut(id)?;
+        meta.tags.retain(|t| !t.eq_ignore_ascii_case(tag.trim()));
+        Some(())
+    }
+    /// every tag in use with the number of entries carrying it, by name
+    pub fn collections(&self) -> Vec<(String, u32)> {
+        let mut collections: Vec<(String, u32)> = vec![];
+        for tag in self.meta.values().flat_map(|meta| &meta.tags) {
+            match collections
+                .iter_mut()
+                .find(|(t, _)| t.eq_ignore_ascii_case(tag))
+            {
+                Some((_, count)) => *count += 1,
+                None => collections.push((tag.clone(), 1)),
+            }
+        }
+        collections.sort_by_key(|(tag, _)| tag.to_lowercase());
+        collections
+    }
+    /// replaces the value of an entry in place, returns its new id
+    pub fn update(&mut self, id: &str, data: Data) -> Option<String> {
+        let node = self.get_mutex(id)?;
+        node.write().unwrap().set_value(data);
+        Some(self.rekey(id, node))
+    }
+    /// restores a previous value of an entry, returns its new id
+    pub fn revert(&mut self, id: &str, index: usize) -> Option<String> {
+        let node = self.get_mutex(id)?;
+        node.write().unwrap().revert(index)?;
+        Some(self.rekey(id, node))
+    }
+    // ids are the hash of the value, so an edited entry moves to a new key. an existing entry
+    // with the same value is dropped, like a repeated insert
+    fn rekey(&mut self, id: &str, node: Arc<RwLock<Node<Data>>>) -> String {
+        let data = node.read().unwrap().val();
+        let new_id = data.hash();
+        if new_id != id {
+            if let Some(existing) = self.get_mutex(&new_id) {
+                self.list.delete(existing);
+            }
+            self.hash.remove(id);
+            self.hash.insert(new_id.clone(), Arc::downgrade(&node));
+            if let Some(meta) = self.meta.remove(id) {
+                self.meta.insert(new_id.clone(), meta);
+            }
+        }
+        self.index.remove(id);
+        self.index.insert(&new_id, &data.val());
+        new_id
+    }
     pub fn delete(&mut self, id: &str) -> Result<(), ()> {
         match self.hash.get(id).cloned() {
             Some(node) => {
                 self.hash.remove(id);
+                self.index.remove(id);
+                self.meta.remove(id);
                 if let Some(data) = node.upgrade() {
                     self.list.delete(data);
                     Ok(())
@@ -84,6 +211,10 @@ impl Lru {
         }
     }
 
+    /// entries kept before the least recently used are evicted
+    pub fn capacity(&self) -> usize {
+        self.size
+    }
     pub fn len(&self) -> usize {
         self.list.len()
     }
@@ -120,4 +251,66 @@ mod test {
         );
         // println!("{:#?}", lru)
     }
+
+    #[test]
+    fn test_index() {
+        let mut lru = Lru::new(2);
+        let data = lru.insert("data".into());
+        let email = lru.insert("nithin@gmail.com".into());
+        assert_eq!(
+            lru.index().substring("a"),
+            vec![email.clone(), data.clone()]
+        );
+        // reinserting moves the entry to the front of the index as well
+        lru.insert("data".into());
+        assert_eq!(lru.index().substring("a"), vec![data, email]);
+        // evicted entries leave the index
+        lru.insert("9449352583".into());
+        assert_eq!(lru.index().len(), 2);
+        assert!(lru.index().substring("gmail").is_empty());
+    }
+
+    #[test]
+    fn test_tags() {
+        let mut lru = Lru::new(2);
+        let a = lru.insert("kubectl apply -f deploy.yaml".into());
+        lru.tag(&a, "Deploy").unwrap();
+        lru.tag(&a, "deploy ").unwrap();
+        assert!(lru.tag(&a, " ").is_none());
+        assert_eq!(lru.meta(&a).unwrap().tags, vec!["Deploy"]);
+        assert_eq!(lru.collections(), vec![("Deploy".to_string(), 1)]);
+
+        // the oldest entry is evicted unless tagged entries are protected
+        let b = lru.insert("b".into());
+        lru.insert("c".into());
+        assert!(lru.get(&a).is_none());
+
+        let insert =
+            |lru: &mut Lru, value: &str| lru.insert_protecting(Data::from(value.to_string()), true);
+        let a = insert(&mut lru, "kubectl apply -f deploy.yaml");
+        lru.tag(&a, "deploy").unwrap();
+        insert(&mut lru, "d");
+        insert(&mut lru, "e");
+        assert!(lru.get(&a).is_some());
+        assert!(lru.get(&b).is_none());
+        assert_eq!(lru.len(), 2);
+        lru.untag(&a, "DEPLOY").unwrap();
+        assert!(lru.collections().is_empty());
+    }
+
+    #[test]
+    fn test_update() {
+        let mut lru = Lru::new(3);
+        let a = lru.insert("first".into());
+        let b = lru.insert("second".into());
+        let a = lru.update(&a, Data::from("second".to_string())).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(lru.len(), 1);
+        assert_eq!(lru.meta(&a).unwrap().copy_count, 1);
+        assert_eq!(lru.index().substring("sec"), vec![a.clone()]);
+        let a = lru.revert(&a, 0).unwrap();
+        assert_eq!(lru.get(&a).unwrap().val(), "first");
+        assert_eq!(lru.index().substring("first"), vec![a]);
+        assert!(lru.index().substring("second").is_empty());
+    }
 }
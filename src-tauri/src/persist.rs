@@ -0,0 +1,56 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::Path;
+
+/// reads json written by `save`. a missing file is `None`, one that does not parse or
+/// validate is kept aside as `.bak` rather than overwritten on the next save
+pub fn load<T: DeserializeOwned>(
+    path: &Path,
+    validate: impl FnOnce(&T) -> Result<(), String>,
+) -> Result<Option<T>, String> {
+    let Ok(text) = std::fs::read_to_string(path) else {
+        return Ok(None);
+    };
+    let value = serde_json::from_str::<T>(&text)
+        .map_err(|e| e.to_string())
+        .and_then(|value| validate(&value).map(|_| value));
+    match value {
+        Ok(value) => Ok(Some(value)),
+        Err(err) => {
+            let _ = std::fs::rename(path, path.with_extension("json.bak"));
+            Err(format!("invalid {}: {}", path.display(), err))
+        }
+    }
+}
+
+pub fn save(path: &Path, value: &impl Serialize) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    // write then rename so a crash mid write keeps the previous file
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, json).map_err(|e| e.to_string())?;
+    std::fs::rename(&tmp, path).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_load_save() {
+        let path = std::env::temp_dir().join(format!("persist-{}.json", uuid::Uuid::new_v4()));
+        let positive = |n: &i32| (*n > 0).then_some(()).ok_or("not positive".to_string());
+        assert_eq!(load::<i32>(&path, positive), Ok(None));
+        save(&path, &1).unwrap();
+        assert_eq!(load::<i32>(&path, positive), Ok(Some(1)));
+
+        save(&path, &-1).unwrap();
+        assert!(load::<i32>(&path, positive).is_err());
+        let backup = path.with_extension("json.bak");
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), "-1");
+        assert_eq!(load::<i32>(&path, positive), Ok(None));
+        let _ = std::fs::remove_file(backup);
+    }
+}
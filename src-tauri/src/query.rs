@@ -0,0 +1,181 @@
+use crate::data::{Data, ProgrammingLanguage};
+use crate::lru_multi_thread::EntryMeta;
+use chrono::{Local, NaiveDate};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+    /// `Data::kind`, eg `type:json`
+    Type(String),
+    Lang(ProgrammingLanguage),
+    /// last copied at or after, unix seconds
+    After(u64),
+    /// last copied before, unix seconds
+    Before(u64),
+    Pinned(bool),
+    Tag(String),
+}
+
+/// parsed search query, eg `type:json lang:rust after:2024-10-01 pinned:true "exact phrase" -excluded`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Query {
+    /// remaining words, fuzzy matched
+    pub text: String,
+    pub phrases: Vec<String>,
+    pub excluded: Vec<String>,
+    pub filters: Vec<Filter>,
+}
+
+/// splits on whitespace, keeping double quoted phrases together with their quotes
+fn words(input: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in input.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                current.push(c);
+            }
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    words.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn unquote(word: &str) -> Option<&str> {
+    word.strip_prefix('"')
+        .map(|word| word.strip_suffix('"').unwrap_or(word))
+}
+
+/// start of the day in local time
+fn date(value: &str) -> Result<u64, String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .and_then(|date| date.and_local_timezone(Local).earliest())
+        .map(|date| date.timestamp().max(0) as u64)
+        .ok_or_else(|| format!("invalid date {}, expected YYYY-MM-DD", value))
+}
+
+fn filter(key: &str, value: &str) -> Option<Result<Filter, String>> {
+    let filter = match key {
+        "type" => Ok(Filter::Type(value.to_lowercase())),
+        "lang" => serde_json::from_value(serde_json::Value::String(value.to_lowercase()))
+            .map(Filter::Lang)
+            .map_err(|_| format!("unknown language {}", value)),
+        "after" => date(value).map(Filter::After),
+        "before" => date(value).map(Filter::Before),
+        "pinned" => value
+            .parse()
+            .map(Filter::Pinned)
+            .map_err(|_| format!("pinned expects true or false, got {}", value)),
+        "tag" => Ok(Filter::Tag(value.to_string())),
+        // the clipboard api reports no source app, fail rather than silently match nothing
+        "app" => Err("app: filter is not supported, the source app is not recorded".to_string()),
+        _ => return None,
+    };
+    Some(filter)
+}
+
+pub fn parse(input: &str) -> Result<Query, String> {
+    let mut query = Query::default();
+    let mut text = vec![];
+    for word in words(input) {
+        if let Some(phrase) = word.strip_prefix('-').filter(|w| !w.is_empty()) {
+            let phrase = unquote(phrase).unwrap_or(phrase);
+            query.excluded.push(phrase.to_lowercase());
+        } else if let Some(phrase) = unquote(&word) {
+            if !phrase.is_empty() {
+                query.phrases.push(phrase.to_lowercase());
+            }
+        } else if let Some(filter) = word
+            .split_once(':')
+            .filter(|(_, value)| !value.is_empty())
+            .and_then(|(key, value)| filter(&key.to_lowercase(), value))
+        {
+            query.filters.push(filter?);
+        } else {
+            // unknown keys such as `https:` are plain text
+            text.push(word);
+        }
+    }
+    query.text = text.join(" ");
+    Ok(query)
+}
+
+impl Query {
+    /// phrase and exclusion checks against the lowercased entry text
+    pub fn matches_text(&self, text: &str) -> bool {
+        self.phrases
+            .iter()
+            .all(|phrase| text.contains(phrase.as_str()))
+            && !self
+                .excluded
+                .iter()
+                .any(|word| text.contains(word.as_str()))
+    }
+
+    pub fn matches_entry(&self, data: &Data, meta: &EntryMeta) -> bool {
+        self.filters.iter().all(|filter| match filter {
+            Filter::Type(kind) => data.kind() == kind,
+            Filter::Lang(lang) => data.lang() == Some(*lang),
+            Filter::After(time) => meta.last_copied >= *time,
+            Filter::Before(time) => meta.last_copied < *time,
+            Filter::Pinned(pinned) => meta.pinned == *pinned,
+            Filter::Tag(tag) => meta.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let query = parse(
+            r#"type:json lang:Rust after:2024-10-01 pinned:true "exact Phrase" -excluded deploy https://a.co"#,
+        )
+        .unwrap();
+        assert_eq!(query.text, "deploy https://a.co");
+        assert_eq!(query.phrases, vec!["exact phrase"]);
+        assert_eq!(query.excluded, vec!["excluded"]);
+        assert_eq!(query.filters.len(), 4);
+        assert_eq!(query.filters[1], Filter::Lang(ProgrammingLanguage::Rust));
+        assert!(parse("lang:klingon").is_err());
+        assert!(parse("after:yesterday").is_err());
+        assert!(parse("app:slack").is_err());
+
+        let meta = EntryMeta {
+            last_copied: date("2024-10-02").unwrap(),
+            pinned: true,
+            ..Default::default()
+        };
+        let data = Data::Code {
+            data: "fn main() {}".into(),
+            lang: ProgrammingLanguage::Rust,
+        };
+        assert!(parse("lang:rust after:2024-10-01 pinned:true")
+            .unwrap()
+            .matches_entry(&data, &meta));
+        assert!(!parse("type:json").unwrap().matches_entry(&data, &meta));
+        let phone = Data::from("+14155552671".to_string());
+        assert!(parse("type:PhoneNumber")
+            .unwrap()
+            .matches_entry(&phone, &meta));
+        assert!(!parse("before:2024-10-01")
+            .unwrap()
+            .matches_entry(&data, &meta));
+        let query = parse(r#""fn main" -unsafe"#).unwrap();
+        assert!(query.matches_text("fn main() {}"));
+        assert!(!query.matches_text("unsafe fn main() {}"));
+    }
+}
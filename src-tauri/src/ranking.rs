@@ -0,0 +1,113 @@
+use crate::lru_multi_thread::EntryMeta;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// weights blended into the search rank, each signal is scaled to 0..1 before weighting
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct Ranking {
+    /// fuzzy match score relative to the best match
+    pub fuzzy: f64,
+    /// how recently the entry was last copied
+    pub recency: f64,
+    /// how often the entry was copied relative to the most copied match
+    pub frequency: f64,
+    pub pinned: f64,
+    /// hours after which the recency signal has halved
+    pub recency_half_life_hours: f64,
+}
+
+impl Default for Ranking {
+    fn default() -> Self {
+        Self {
+            fuzzy: 1.0,
+            recency: 0.3,
+            frequency: 0.2,
+            pinned: 0.5,
+            recency_half_life_hours: 24.0 * 7.0,
+        }
+    }
+}
+
+/// maxima over the candidate set, used to scale the signals
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Bounds {
+    pub max_score: i64,
+    pub max_count: u32,
+}
+
+impl Ranking {
+    pub fn validate(&self) -> Result<(), String> {
+        let weights = [self.fuzzy, self.recency, self.frequency, self.pinned];
+        if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+            return Err("ranking weights must be positive numbers".into());
+        }
+        if !(self.recency_half_life_hours.is_finite() && self.recency_half_life_hours > 0.0) {
+            return Err("recency half life must be greater than zero".into());
+        }
+        Ok(())
+    }
+
+    pub fn rank(&self, score: i64, meta: &EntryMeta, bounds: Bounds, now: u64) -> f64 {
+        let fuzzy = match bounds.max_score {
+            max if max > 0 => score.max(0) as f64 / max as f64,
+            // no text to match, every candidate matched equally
+            _ => 1.0,
+        };
+        let age_hours = now.saturating_sub(meta.last_copied) as f64 / 3600.0;
+        let recency = 0.5_f64.powf(age_hours / self.recency_half_life_hours);
+        let frequency = match bounds.max_count {
+            0 => 0.0,
+            max => (meta.copy_count as f64).ln_1p() / (max as f64).ln_1p(),
+        };
+        let pinned = if meta.pinned { 1.0 } else { 0.0 };
+        self.fuzzy * fuzzy
+            + self.recency * recency
+            + self.frequency * frequency
+            + self.pinned * pinned
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rank() {
+        let ranking = Ranking::default();
+        let now = 1_700_000_000;
+        let bounds = Bounds {
+            max_score: 100,
+            max_count: 50,
+        };
+        let old_one_off = EntryMeta {
+            last_copied: now - 90 * 24 * 3600,
+            copy_count: 1,
+            ..Default::default()
+        };
+        let daily = EntryMeta {
+            last_copied: now - 3600,
+            copy_count: 50,
+            ..Default::default()
+        };
+        // a slightly better match from months ago loses to the snippet used daily
+        assert!(
+            ranking.rank(100, &old_one_off, bounds, now) < ranking.rank(80, &daily, bounds, now)
+        );
+
+        let fuzzy_only = Ranking {
+            recency: 0.0,
+            frequency: 0.0,
+            ..Ranking::default()
+        };
+        assert!(
+            fuzzy_only.rank(100, &old_one_off, bounds, now)
+                > fuzzy_only.rank(80, &daily, bounds, now)
+        );
+        assert!(Ranking {
+            recency_half_life_hours: 0.0,
+            ..Ranking::default()
+        }
+        .validate()
+        .is_err());
+    }
+}
@@ -0,0 +1,145 @@
+use crate::data::Data;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::time::{Duration, Instant};
+
+const MAX_PATTERN_LEN: usize = 1024;
+/// compiled program and lazy dfa limits, the regex crate matches in linear time so these and
+/// the deadline are what keeps a pattern from tying up the search thread
+const SIZE_LIMIT: usize = 1 << 20;
+const DEADLINE: Duration = Duration::from_secs(2);
+/// spans reported per entry
+const MAX_SPANS: usize = 100;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type, PartialEq)]
+#[serde(tag = "kind", content = "content")]
+pub enum RegexError {
+    TooLong { max: u32 },
+    Compile(String),
+}
+
+/// char offsets, end exclusive
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Type, PartialEq)]
+pub struct Span {
+    pub start: u32,
+    pub end: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type, PartialEq)]
+pub struct RegexMatch {
+    pub id: String,
+    pub spans: Vec<Span>,
+    pub data: Data,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type, PartialEq)]
+pub struct RegexSearch {
+    pub matches: Vec<RegexMatch>,
+    /// the deadline was hit before every entry was searched
+    pub timed_out: bool,
+}
+
+pub fn compile(pattern: &str, case_insensitive: bool) -> Result<Regex, RegexError> {
+    if pattern.len() > MAX_PATTERN_LEN {
+        return Err(RegexError::TooLong {
+            max: MAX_PATTERN_LEN as u32,
+        });
+    }
+    RegexBuilder::new(pattern)
+        .case_insensitive(case_insensitive)
+        .size_limit(SIZE_LIMIT)
+        .dfa_size_limit(SIZE_LIMIT)
+        .build()
+        .map_err(|err| RegexError::Compile(err.to_string()))
+}
+
+pub fn spans(regex: &Regex, text: &str) -> Vec<Span> {
+    let (mut byte, mut char) = (0, 0);
+    let mut to_char = |offset: usize| {
+        char += text[byte..offset].chars().count();
+        byte = offset;
+        char as u32
+    };
+    regex
+        .find_iter(text)
+        .filter(|m| !m.is_empty())
+        .take(MAX_SPANS)
+        .map(|m| Span {
+            start: to_char(m.start()),
+            end: to_char(m.end()),
+        })
+        .collect()
+}
+
+/// entries with at least one match in the given order, skipping `offset` matching entries
+pub fn search(
+    regex: &Regex,
+    entries: impl Iterator<Item = Data>,
+    offset: usize,
+    limit: usize,
+) -> RegexSearch {
+    let started = Instant::now();
+    let mut matches = vec![];
+    let mut skipped = 0;
+    for data in entries {
+        if started.elapsed() > DEADLINE {
+            return RegexSearch {
+                matches,
+                timed_out: true,
+            };
+        }
+        if matches.len() >= limit {
+            break;
+        }
+        // secrets are searched and highlighted in their masked form
+        let id = data.hash();
+        let data = data.masked();
+        let spans = spans(regex, &data.val());
+        if spans.is_empty() {
+            continue;
+        }
+        if skipped < offset {
+            skipped += 1;
+            continue;
+        }
+        matches.push(RegexMatch { id, spans, data });
+    }
+    RegexSearch {
+        matches,
+        timed_out: false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_regex_search() {
+        assert!(matches!(
+            compile("ERR-(", false),
+            Err(RegexError::Compile(_))
+        ));
+        assert!(matches!(
+            compile(&"a".repeat(2000), false),
+            Err(RegexError::TooLong { max: 1024 })
+        ));
+        assert!(matches!(
+            compile(r"\w{1000}{1000}", false),
+            Err(RegexError::Compile(_))
+        ));
+
+        let regex = compile(r"err-\d{5}", true).unwrap();
+        assert_eq!(
+            spans(&regex, "é ERR-12345 and err-54321"),
+            vec![Span { start: 2, end: 11 }, Span { start: 16, end: 25 }]
+        );
+        let entries =
+            ["ERR-00001", "nothing", "ERR-00002", "ERR-00003"].map(|s| Data::from(s.to_string()));
+        let result = search(&regex, entries.into_iter(), 1, 1);
+        assert!(!result.timed_out);
+        assert_eq!(result.matches.len(), 1);
+        assert_eq!(result.matches[0].data.val(), "ERR-00002");
+    }
+}
@@ -0,0 +1,284 @@
+use crate::similarity::{self, Corpus, Vector};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{HashMap, HashSet};
+
+/// only the start of very large entries is indexed
+const MAX_INDEXED_BYTES: usize = 256 * 1024;
+
+type DocId = u32;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Type, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchMode {
+    /// case insensitive substring
+    Substring,
+    /// every word of the query as a whole word
+    Tokens,
+}
+
+#[derive(Debug)]
+struct Doc {
+    id: String,
+    /// lowercased text
+    text: String,
+    /// bumped whenever the entry moves to the front of the history
+    seq: u64,
+    vector: Vector,
+}
+
+/// trigram and token index over the history, kept in sync by `Lru` so queries don't need to
+/// walk the list and clone every entry
+#[derive(Debug, Default)]
+pub struct SearchIndex {
+    ids: HashMap<String, DocId>,
+    docs: HashMap<DocId, Doc>,
+    trigrams: HashMap<[char; 3], HashSet<DocId>>,
+    tokens: HashMap<String, HashSet<DocId>>,
+    corpus: Corpus,
+    /// whether entries get similarity vectors, off skips the work on every insert
+    similarity: bool,
+    next_doc: DocId,
+    next_seq: u64,
+}
+
+fn normalize(text: &str) -> String {
+    let mut end = text.len().min(MAX_INDEXED_BYTES);
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text[..end].to_lowercase()
+}
+
+fn trigrams(text: &str) -> HashSet<[char; 3]> {
+    let chars: Vec<char> = text.chars().collect();
+    chars.windows(3).map(|w| [w[0], w[1], w[2]]).collect()
+}
+
+fn tokens(text: &str) -> HashSet<&str> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .collect()
+}
+
+impl SearchIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, id: &str, text: &str) {
+        self.remove(id);
+        let doc = self.next_doc;
+        self.next_doc += 1;
+        let text = normalize(text);
+        for trigram in trigrams(&text) {
+            self.trigrams.entry(trigram).or_default().insert(doc);
+        }
+        for token in tokens(&text) {
+            self.tokens
+                .entry(token.to_string())
+                .or_default()
+                .insert(doc);
+        }
+        let vector = if self.similarity {
+            let vector = similarity::vectorize(&text);
+            self.corpus.add(&vector);
+            vector
+        } else {
+            vec![]
+        };
+        self.next_seq += 1;
+        self.docs.insert(
+            doc,
+            Doc {
+                id: id.to_string(),
+                text,
+                seq: self.next_seq,
+                vector,
+            },
+        );
+        self.ids.insert(id.to_string(), doc);
+    }
+
+    pub fn remove(&mut self, id: &str) {
+        let Some(doc) = self.ids.remove(id) else {
+            return;
+        };
+        if let Some(removed) = self.docs.remove(&doc) {
+            if self.similarity {
+                self.corpus.remove(&removed.vector);
+            }
+            for trigram in trigrams(&removed.text) {
+                if let Some(set) = self.trigrams.get_mut(&trigram) {
+                    set.remove(&doc);
+                    if set.is_empty() {
+                        self.trigrams.remove(&trigram);
+                    }
+                }
+            }
+            for token in tokens(&removed.text) {
+                if let Some(set) = self.tokens.get_mut(token) {
+                    set.remove(&doc);
+                    if set.is_empty() {
+                        self.tokens.remove(token);
+                    }
+                }
+            }
+        }
+    }
+
+    /// builds the similarity vectors of every entry, or drops them when turned off
+    pub fn set_similarity(&mut self, enabled: bool) {
+        if self.similarity == enabled {
+            return;
+        }
+        self.similarity = enabled;
+        self.corpus = Corpus::default();
+        for doc in self.docs.values_mut() {
+            doc.vector = if enabled {
+                similarity::vectorize(&doc.text)
+            } else {
+                vec![]
+            };
+            if enabled {
+                self.corpus.add(&doc.vector);
+            }
+        }
+    }
+
+    /// marks the entry as most recently used
+    pub fn touch(&mut self, id: &str) {
+        if let Some(doc) = self.ids.get(id).and_then(|doc| self.docs.get_mut(doc)) {
+            self.next_seq += 1;
+            doc.seq = self.next_seq;
+        }
+    }
+
+    fn intersect<'a>(&self, sets: impl Iterator<Item = Option<&'a HashSet<DocId>>>) -> Vec<DocId> {
+        let mut sets: Vec<&HashSet<DocId>> = match sets.collect::<Option<Vec<_>>>() {
+            Some(sets) => sets,
+            None => return vec![],
+        };
+        sets.sort_by_key(|set| set.len());
+        let Some((smallest, rest)) = sets.split_first() else {
+            return vec![];
+        };
+        smallest
+            .iter()
+            .filter(|doc| rest.iter().all(|set| set.contains(doc)))
+            .copied()
+            .collect()
+    }
+
+    fn ids_by_recency(&self, mut docs: Vec<DocId>) -> Vec<String> {
+        docs.sort_by_key(|doc| std::cmp::Reverse(self.docs[doc].seq));
+        docs.into_iter()
+            .map(|doc| self.docs[&doc].id.clone())
+            .collect()
+    }
+
+    /// ids of entries containing `query` case insensitively, most recent first
+    pub fn substring(&self, query: &str) -> Vec<String> {
+        let query = query.to_lowercase();
+        let candidates: Vec<DocId> = if query.chars().count() < 3 {
+            self.docs.keys().copied().collect()
+        } else {
+            self.intersect(
+                trigrams(&query)
+                    .into_iter()
+                    .map(|trigram| self.trigrams.get(&trigram)),
+            )
+        };
+        let matches = candidates
+            .into_iter()
+            .filter(|doc| self.docs[doc].text.contains(&query))
+            .collect();
+        self.ids_by_recency(matches)
+    }
+
+    /// ids of entries containing every word of `query` as a whole token, most recent first
+    pub fn tokens(&self, query: &str) -> Vec<String> {
+        let query = query.to_lowercase();
+        let words = tokens(&query);
+        if words.is_empty() {
+            return vec![];
+        }
+        let matches = self.intersect(words.into_iter().map(|word| self.tokens.get(word)));
+        self.ids_by_recency(matches)
+    }
+
+    pub fn query(&self, query: &str, mode: SearchMode) -> Vec<String> {
+        match mode {
+            SearchMode::Substring => self.substring(query),
+            SearchMode::Tokens => self.tokens(query),
+        }
+    }
+
+    /// ids of the `limit` entries closest to `query` by tf-idf cosine similarity, best first.
+    /// `None` when similarity search is turned off
+    pub fn similar(&self, query: &str, limit: usize) -> Option<Vec<(String, f64)>> {
+        if !self.similarity {
+            return None;
+        }
+        let query = self.corpus.weigh(&similarity::vectorize(&normalize(query)));
+        let mut scored: Vec<(&Doc, f64)> = self
+            .docs
+            .values()
+            .map(|doc| (doc, query.cosine(&self.corpus.weigh(&doc.vector))))
+            .filter(|(_, score)| *score > 0.0)
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1).then(b.0.seq.cmp(&a.0.seq)));
+        let similar = scored
+            .into_iter()
+            .take(limit)
+            .map(|(doc, score)| (doc.id.clone(), score))
+            .collect();
+        Some(similar)
+    }
+
+    /// lowercased text of every entry with its id, most recent first
+    pub fn texts(&self) -> Vec<(&str, &str)> {
+        let mut docs: Vec<&Doc> = self.docs.values().collect();
+        docs.sort_by_key(|doc| std::cmp::Reverse(doc.seq));
+        docs.into_iter()
+            .map(|doc| (doc.id.as_str(), doc.text.as_str()))
+            .collect()
+    }
+
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.docs.len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_index() {
+        let mut index = SearchIndex::new();
+        index.insert("a", "kubectl get pods -n Deploy");
+        assert!(index.similar("pod", 1).is_none());
+        index.set_similarity(true);
+        index.insert("b", "git push origin deploy-branch");
+        index.insert("c", "cargo build");
+        assert_eq!(index.substring("DEPLOY"), vec!["b", "a"]);
+        assert_eq!(index.substring("pods"), vec!["a"]);
+        assert_eq!(index.substring("de"), vec!["b", "a"]);
+        assert_eq!(index.tokens("deploy"), vec!["b", "a"]);
+        assert_eq!(index.tokens("git deploy"), vec!["b"]);
+        assert!(index.tokens("depl").is_empty());
+        assert_eq!(index.similar("pod deployment", 1).unwrap()[0].0, "a");
+
+        index.touch("a");
+        assert_eq!(index.substring("deploy"), vec!["a", "b"]);
+
+        index.remove("a");
+        assert_eq!(index.substring("deploy"), vec!["b"]);
+        index.insert("b", "renamed");
+        assert!(index.substring("deploy").is_empty());
+        assert_eq!(index.len(), 2);
+        assert!(!index.trigrams.contains_key(&['d', 'e', 'p']));
+    }
+}
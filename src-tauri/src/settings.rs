@@ -0,0 +1,85 @@
+use crate::detector::DetectorKind;
+use crate::persist;
+use crate::ranking::Ranking;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::str::FromStr as _;
+use std::sync::{LazyLock, OnceLock, RwLock};
+
+/// fields missing from an older settings file keep their defaults
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct Settings {
+    /// ISO 3166-1 alpha-2 region used for phone numbers without a country code
+    pub phone_region: String,
+    /// detectors skipped when classifying new entries
+    pub disabled_detectors: Vec<DetectorKind>,
+    /// overrides of the built in detector priorities, lower runs first
+    pub detector_priorities: HashMap<DetectorKind, u8>,
+    /// how search blends match quality with recency, copy count and pins
+    pub ranking: Ranking,
+    /// keep tagged entries when the history is full
+    pub protect_tagged: bool,
+    /// index entries for `similar_search`
+    pub similarity_search: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            phone_region: "US".into(),
+            disabled_detectors: vec![],
+            detector_priorities: HashMap::new(),
+            ranking: Ranking::default(),
+            protect_tagged: false,
+            similarity_search: true,
+        }
+    }
+}
+
+impl Settings {
+    pub fn validate(&self) -> Result<(), String> {
+        self.ranking.validate()?;
+        phonenumber::country::Id::from_str(&self.phone_region)
+            .map(|_| ())
+            .map_err(|_| format!("unknown region {}", self.phone_region))
+    }
+}
+
+// classification runs inside `Data::from` which has no access to tauri state,
+// so settings live in a process wide lock
+static SETTINGS: LazyLock<RwLock<Settings>> = LazyLock::new(|| RwLock::new(Settings::default()));
+/// set once at startup, without it settings only live in memory
+static PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// reads the saved settings, a missing file keeps the defaults, see `persist::load` for
+/// unreadable ones. the defaults stay in place when this fails
+pub fn load(path: PathBuf) -> Result<(), String> {
+    let loaded = persist::load(&path, Settings::validate);
+    let _ = PATH.set(path);
+    if let Some(settings) = loaded? {
+        *SETTINGS.write().unwrap() = settings;
+    }
+    Ok(())
+}
+
+fn save(settings: &Settings) -> Result<(), String> {
+    match PATH.get() {
+        Some(path) => persist::save(path, settings),
+        None => Ok(()),
+    }
+}
+
+pub fn get() -> Settings {
+    SETTINGS.read().unwrap().clone()
+}
+
+pub fn set(settings: Settings) -> Result<(), String> {
+    settings.validate()?;
+    let mut current = SETTINGS.write().unwrap();
+    save(&settings)?;
+    *current = settings;
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+/// features are hashed into this many buckets, collisions are rare enough at history sizes
+const BUCKETS: u32 = 1 << 12;
+
+/// term counts per bucket, sorted by bucket
+pub type Vector = Vec<(u32, u32)>;
+
+// fnv-1a, stable across runs unlike the std hasher
+fn bucket(feature: &str) -> u32 {
+    let hash = feature.bytes().fold(0x811c9dc5_u32, |hash, byte| {
+        (hash ^ byte as u32).wrapping_mul(0x01000193)
+    });
+    hash % BUCKETS
+}
+
+/// words plus the char trigrams of each word, so `deploy` and `deployment` still overlap.
+/// expects lowercased text
+pub fn vectorize(text: &str) -> Vector {
+    let mut counts: HashMap<u32, u32> = HashMap::new();
+    for word in text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        *counts.entry(bucket(word)).or_default() += 1;
+        let padded: Vec<char> = format!("^{}$", word).chars().collect();
+        for trigram in padded.windows(3) {
+            let trigram: String = trigram.iter().collect();
+            *counts.entry(bucket(&trigram)).or_default() += 1;
+        }
+    }
+    let mut vector: Vector = counts.into_iter().collect();
+    vector.sort_unstable();
+    vector
+}
+
+/// document frequency per bucket across the indexed entries
+#[derive(Debug, Default)]
+pub struct Corpus {
+    docs: u32,
+    df: HashMap<u32, u32>,
+}
+
+impl Corpus {
+    pub fn add(&mut self, vector: &Vector) {
+        self.docs += 1;
+        for (bucket, _) in vector {
+            *self.df.entry(*bucket).or_default() += 1;
+        }
+    }
+
+    pub fn remove(&mut self, vector: &Vector) {
+        self.docs = self.docs.saturating_sub(1);
+        for (bucket, _) in vector {
+            if let Some(df) = self.df.get_mut(bucket) {
+                *df -= 1;
+                if *df == 0 {
+                    self.df.remove(bucket);
+                }
+            }
+        }
+    }
+
+    /// tf-idf weights of `vector` against the current corpus
+    pub fn weigh(&self, vector: &Vector) -> Weighted {
+        let weights: Vec<(u32, f64)> = vector
+            .iter()
+            .map(|(bucket, count)| {
+                let df = self.df.get(bucket).copied().unwrap_or_default() as f64;
+                let idf = ((self.docs as f64 + 1.0) / (df + 1.0)).ln() + 1.0;
+                (*bucket, (1.0 + (*count as f64).ln()) * idf)
+            })
+            .collect();
+        let norm = weights.iter().map(|(_, w)| w * w).sum::<f64>().sqrt();
+        Weighted { weights, norm }
+    }
+}
+
+/// weighted vector with its norm, a query is weighed once and compared against every entry
+#[derive(Debug)]
+pub struct Weighted {
+    weights: Vec<(u32, f64)>,
+    norm: f64,
+}
+
+impl Weighted {
+    /// cosine similarity of the tf-idf weighted vectors, 0..1
+    pub fn cosine(&self, other: &Weighted) -> f64 {
+        if self.norm == 0.0 || other.norm == 0.0 {
+            return 0.0;
+        }
+        let (a, b) = (&self.weights, &other.weights);
+        // both sorted by bucket
+        let (mut i, mut j, mut dot) = (0, 0, 0.0);
+        while i < a.len() && j < b.len() {
+            match a[i].0.cmp(&b[j].0) {
+                std::cmp::Ordering::Less => i += 1,
+                std::cmp::Ordering::Greater => j += 1,
+                std::cmp::Ordering::Equal => {
+                    dot += a[i].1 * b[j].1;
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        dot / (self.norm * other.norm)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_similarity() {
+        let docs = [
+            "kubectl rollout restart deployment api",
+            "select * from users where email = ?",
+            "git push origin main",
+        ]
+        .map(vectorize);
+        let mut corpus = Corpus::default();
+        docs.iter().for_each(|doc| corpus.add(doc));
+
+        let similarity = |a: &Vector, b: &Vector| corpus.weigh(a).cosine(&corpus.weigh(b));
+        let query = vectorize("restart the api deployments");
+        let scores: Vec<f64> = docs.iter().map(|doc| similarity(&query, doc)).collect();
+        assert!(scores[0] > scores[1] && scores[0] > scores[2]);
+        assert!((similarity(&docs[2], &docs[2]) - 1.0).abs() < 1e-9);
+        assert_eq!(similarity(&vectorize(""), &docs[0]), 0.0);
+
+        corpus.remove(&docs[1]);
+        assert_eq!(corpus.docs, 2);
+    }
+}
@@ -0,0 +1,209 @@
+use crate::lru_multi_thread::now;
+use crate::persist;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::PathBuf;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct Snippet {
+    pub id: String,
+    pub name: String,
+    /// short unique trigger, eg `;sig`
+    pub abbreviation: Option<String>,
+    /// `/` separated path, eg `work/email`
+    pub folder: Option<String>,
+    pub text: String,
+    /// unix seconds
+    pub created: u64,
+}
+
+/// reusable text kept apart from the history, saved as json after every change
+#[derive(Debug, Default)]
+pub struct SnippetStore {
+    /// `None` keeps the store in memory only
+    path: Option<PathBuf>,
+    snippets: Vec<Snippet>,
+}
+
+fn clean(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().trim_matches('/').to_string())
+        .filter(|value| !value.is_empty())
+}
+
+impl SnippetStore {
+    /// empty store saved to `path`
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path: Some(path),
+            snippets: vec![],
+        }
+    }
+
+    /// a missing file is an empty store, see `persist::load` for unreadable ones
+    pub fn load(path: PathBuf) -> Result<Self, String> {
+        let snippets = persist::load(&path, |_: &Vec<Snippet>| Ok(()))?.unwrap_or_default();
+        Ok(Self {
+            path: Some(path),
+            snippets,
+        })
+    }
+
+    fn save(&self) -> Result<(), String> {
+        match &self.path {
+            Some(path) => persist::save(path, &self.snippets),
+            None => Ok(()),
+        }
+    }
+
+    fn validate(&self, snippet: &Snippet) -> Result<(), String> {
+        if snippet.name.trim().is_empty() {
+            return Err("snippet name is empty".into());
+        }
+        if let Some(abbreviation) = &snippet.abbreviation {
+            let taken = self.snippets.iter().any(|other| {
+                other.id != snippet.id && other.abbreviation.as_ref() == Some(abbreviation)
+            });
+            if taken {
+                return Err(format!("abbreviation {} is already used", abbreviation));
+            }
+        }
+        Ok(())
+    }
+
+    /// snippets in `folder` and its subfolders, or all of them, by name
+    pub fn list(&self, folder: Option<&str>) -> Vec<Snippet> {
+        let folder = folder.map(|folder| folder.trim_matches('/'));
+        let mut snippets: Vec<Snippet> = self
+            .snippets
+            .iter()
+            .filter(|snippet| match (folder, &snippet.folder) {
+                (None, _) => true,
+                (Some(folder), Some(path)) => {
+                    path == folder || path.starts_with(&format!("{}/", folder))
+                }
+                (Some(_), None) => false,
+            })
+            .cloned()
+            .collect();
+        snippets.sort_by_key(|snippet| snippet.name.to_lowercase());
+        snippets
+    }
+
+    pub fn folders(&self) -> Vec<String> {
+        let mut folders: Vec<String> = self
+            .snippets
+            .iter()
+            .filter_map(|snippet| snippet.folder.clone())
+            .collect();
+        folders.sort();
+        folders.dedup();
+        folders
+    }
+
+    /// looks a snippet up by id or abbreviation
+    pub fn get(&self, key: &str) -> Option<&Snippet> {
+        self.snippets
+            .iter()
+            .find(|snippet| snippet.id == key || snippet.abbreviation.as_deref() == Some(key))
+    }
+
+    pub fn create(
+        &mut self,
+        name: String,
+        text: String,
+        abbreviation: Option<String>,
+        folder: Option<String>,
+    ) -> Result<Snippet, String> {
+        let snippet = Snippet {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: name.trim().to_string(),
+            abbreviation: clean(abbreviation),
+            folder: clean(folder),
+            text,
+            created: now(),
+        };
+        self.validate(&snippet)?;
+        self.snippets.push(snippet.clone());
+        self.save()?;
+        Ok(snippet)
+    }
+
+    pub fn update(&mut self, snippet: Snippet) -> Result<(), String> {
+        let snippet = Snippet {
+            name: snippet.name.trim().to_string(),
+            abbreviation: clean(snippet.abbreviation),
+            folder: clean(snippet.folder),
+            ..snippet
+        };
+        self.validate(&snippet)?;
+        let existing = self
+            .snippets
+            .iter_mut()
+            .find(|existing| existing.id == snippet.id)
+            .ok_or_else(|| "snippet not found".to_string())?;
+        *existing = snippet;
+        self.save()
+    }
+
+    pub fn delete(&mut self, id: &str) -> Result<(), String> {
+        let len = self.snippets.len();
+        self.snippets.retain(|snippet| snippet.id != id);
+        if self.snippets.len() == len {
+            return Err("snippet not found".into());
+        }
+        self.save()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_store() {
+        let path = std::env::temp_dir().join(format!("snippets-{}.json", uuid::Uuid::new_v4()));
+        let mut store = SnippetStore::load(path.clone()).unwrap();
+        let sig = store
+            .create(
+                "Signature".into(),
+                "Regards".into(),
+                Some(";sig".into()),
+                Some("/work/email/".into()),
+            )
+            .unwrap();
+        assert_eq!(sig.folder.as_deref(), Some("work/email"));
+        assert!(store
+            .create("Other".into(), "x".into(), Some(";sig".into()), None)
+            .is_err());
+        assert!(store.create(" ".into(), "x".into(), None, None).is_err());
+        store
+            .create("deploy".into(), "kubectl apply".into(), None, None)
+            .unwrap();
+
+        let store = SnippetStore::load(path.clone()).unwrap();
+        assert_eq!(store.list(None).len(), 2);
+        assert_eq!(store.list(Some("work")), vec![sig.clone()]);
+        assert!(store.list(Some("wor")).is_empty());
+        assert_eq!(store.get(";sig"), Some(&sig));
+        assert_eq!(store.folders(), vec!["work/email"]);
+
+        let mut store = store;
+        store.delete(&sig.id).unwrap();
+        assert!(store.delete(&sig.id).is_err());
+        assert_eq!(
+            SnippetStore::load(path.clone()).unwrap().list(None).len(),
+            1
+        );
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(SnippetStore::load(path.clone()).is_err());
+        let backup = path.with_extension("json.bak");
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), "not json");
+        assert!(SnippetStore::load(path.clone())
+            .unwrap()
+            .list(None)
+            .is_empty());
+        let _ = std::fs::remove_file(backup);
+    }
+}
@@ -0,0 +1,124 @@
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part<'a> {
+    Text(&'a str),
+    /// strftime format, `{{date}}` is `%Y-%m-%d`
+    Date(&'a str),
+    Clipboard,
+    Uuid,
+    Input(&'a str),
+}
+
+/// placeholders are `{{name}}` or `{{name:argument}}`, unknown ones are kept as written
+fn parse(template: &str) -> Vec<Part<'_>> {
+    let mut parts = vec![];
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start..].find("}}") else {
+            break;
+        };
+        let placeholder = &rest[start..start + len + 2];
+        let inner = placeholder[2..placeholder.len() - 2].trim();
+        let (name, argument) = match inner.split_once(':') {
+            Some((name, argument)) => (name.trim(), Some(argument.trim())),
+            None => (inner, None),
+        };
+        let part = match (name, argument) {
+            ("date", None) => Part::Date("%Y-%m-%d"),
+            ("time", None) => Part::Date("%H:%M"),
+            ("date", Some(format)) => Part::Date(format),
+            ("clipboard", None) => Part::Clipboard,
+            ("uuid", None) => Part::Uuid,
+            ("input", Some(label)) if !label.is_empty() => Part::Input(label),
+            _ => Part::Text(placeholder),
+        };
+        parts.push(Part::Text(&rest[..start]));
+        parts.push(part);
+        rest = &rest[start + len + 2..];
+    }
+    parts.push(Part::Text(rest));
+    parts
+}
+
+/// labels of the `{{input:Label}}` placeholders the user has to fill in, in order
+pub fn inputs(template: &str) -> Vec<String> {
+    let mut labels: Vec<String> = vec![];
+    for part in parse(template) {
+        if let Part::Input(label) = part {
+            if !labels.iter().any(|l| l == label) {
+                labels.push(label.to_string());
+            }
+        }
+    }
+    labels
+}
+
+pub fn uses_clipboard(template: &str) -> bool {
+    parse(template).contains(&Part::Clipboard)
+}
+
+pub struct Context {
+    pub now: DateTime<Local>,
+    pub clipboard: String,
+    pub inputs: HashMap<String, String>,
+}
+
+pub fn render(template: &str, context: &Context) -> Result<String, String> {
+    let mut out = String::with_capacity(template.len());
+    for part in parse(template) {
+        match part {
+            Part::Text(text) => out.push_str(text),
+            Part::Date(format) => {
+                // invalid specifiers would make `to_string` panic
+                let items: Vec<Item> = StrftimeItems::new(format).collect();
+                if items.contains(&Item::Error) {
+                    return Err(format!("invalid date format {}", format));
+                }
+                out.push_str(&context.now.format_with_items(items.into_iter()).to_string());
+            }
+            Part::Clipboard => out.push_str(&context.clipboard),
+            Part::Uuid => out.push_str(&uuid::Uuid::new_v4().to_string()),
+            Part::Input(label) => out.push_str(
+                context
+                    .inputs
+                    .get(label)
+                    .ok_or_else(|| format!("missing input {}", label))?,
+            ),
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::TimeZone as _;
+
+    #[test]
+    fn test_render() {
+        let template =
+            "{{input:Ticket}} on {{date}} at {{ time }} by {{input: Ticket }}: {{clipboard}} {{other}} {{";
+        assert_eq!(inputs(template), vec!["Ticket"]);
+        assert!(uses_clipboard(template));
+        let mut context = Context {
+            now: Local.with_ymd_and_hms(2024, 10, 1, 9, 5, 0).unwrap(),
+            clipboard: "copied".into(),
+            inputs: HashMap::new(),
+        };
+        assert_eq!(
+            render(template, &context),
+            Err("missing input Ticket".into())
+        );
+        context.inputs.insert("Ticket".into(), "OPS-42".into());
+        assert_eq!(
+            render(template, &context).unwrap(),
+            "OPS-42 on 2024-10-01 at 09:05 by OPS-42: copied {{other}} {{"
+        );
+        assert_eq!(render("{{date:%d/%m}}", &context).unwrap(), "01/10");
+        assert!(render("{{date:%Q}}", &context).is_err());
+        assert_eq!(render("{{uuid}}", &context).unwrap().len(), 36);
+    }
+}
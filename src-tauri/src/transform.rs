@@ -0,0 +1,270 @@
+use heck::{
+    ToKebabCase as _, ToLowerCamelCase as _, ToShoutySnakeCase as _, ToSnakeCase as _,
+    ToTitleCase as _, ToUpperCamelCase as _,
+};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Type, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Case {
+    Camel,
+    Pascal,
+    Snake,
+    Kebab,
+    Constant,
+    Title,
+    Upper,
+    Lower,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Type, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Escape {
+    Json,
+    Rust,
+    Shell,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Type, PartialEq)]
+#[serde(tag = "tag", content = "content")]
+pub enum Transform {
+    /// applied line by line
+    Case(Case),
+    Trim,
+    TrimLines,
+    SortLines,
+    DedupeLines,
+    ReverseLines,
+    Reverse,
+    Escape(Escape),
+    Unescape(Escape),
+    /// wraps lines at the given column, words longer than the column are kept whole
+    Wrap(u32),
+}
+
+fn map_lines(text: &str, f: impl Fn(&str) -> String) -> String {
+    text.lines().map(f).collect::<Vec<_>>().join("\n")
+}
+
+fn change_case(text: &str, case: Case) -> String {
+    match case {
+        Case::Camel => map_lines(text, |line| line.to_lower_camel_case()),
+        Case::Pascal => map_lines(text, |line| line.to_upper_camel_case()),
+        Case::Snake => map_lines(text, |line| line.to_snake_case()),
+        Case::Kebab => map_lines(text, |line| line.to_kebab_case()),
+        Case::Constant => map_lines(text, |line| line.to_shouty_snake_case()),
+        Case::Title => map_lines(text, |line| line.to_title_case()),
+        Case::Upper => text.to_uppercase(),
+        Case::Lower => text.to_lowercase(),
+    }
+}
+
+fn wrap(text: &str, width: usize) -> String {
+    let width = width.max(1);
+    map_lines(text, |line| {
+        let mut lines = vec![];
+        let mut current = String::new();
+        for word in line.split_whitespace() {
+            if !current.is_empty() && current.chars().count() + 1 + word.chars().count() > width {
+                lines.push(std::mem::take(&mut current));
+            }
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(word);
+        }
+        lines.push(current);
+        lines.join("\n")
+    })
+}
+
+fn escape(text: &str, escape: Escape) -> String {
+    match escape {
+        Escape::Json => {
+            let quoted = serde_json::to_string(text).unwrap_or_default();
+            quoted[1..quoted.len() - 1].to_string()
+        }
+        Escape::Rust => text.escape_debug().to_string(),
+        Escape::Shell => format!("'{}'", text.replace('\'', r"'\''")),
+    }
+}
+
+fn unescape_json(text: &str) -> Result<String, String> {
+    let quoted = if text.len() >= 2 && text.starts_with('"') && text.ends_with('"') {
+        text.to_string()
+    } else {
+        format!("\"{}\"", text)
+    };
+    serde_json::from_str::<String>(&quoted).map_err(|e| e.to_string())
+}
+
+fn unescape_rust(text: &str) -> Result<String, String> {
+    let text = text
+        .strip_prefix('"')
+        .and_then(|t| t.strip_suffix('"'))
+        .unwrap_or(text);
+    let mut out = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('t') => out.push('\t'),
+            Some('0') => out.push('\0'),
+            Some(c @ ('\\' | '\'' | '"')) => out.push(c),
+            Some('x') => {
+                let hex: String = chars.by_ref().take(2).collect();
+                let byte = u8::from_str_radix(&hex, 16)
+                    .ok()
+                    .filter(u8::is_ascii)
+                    .ok_or_else(|| format!("invalid escape \\x{}", hex))?;
+                out.push(byte as char);
+            }
+            Some('u') => {
+                if chars.next() != Some('{') {
+                    return Err("invalid unicode escape".into());
+                }
+                let hex: String = chars.by_ref().take_while(|c| *c != '}').collect();
+                let c = u32::from_str_radix(&hex.replace('_', ""), 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| format!("invalid escape \\u{{{}}}", hex))?;
+                out.push(c);
+            }
+            // line continuation skips the newline and leading whitespace
+            Some('\n') => while chars.next_if(|c| c.is_whitespace()).is_some() {},
+            Some(c) => return Err(format!("unknown escape \\{}", c)),
+            None => return Err("trailing backslash".into()),
+        }
+    }
+    Ok(out)
+}
+
+/// removes posix shell quoting, whitespace outside of quotes is kept as is
+fn unescape_shell(text: &str) -> Result<String, String> {
+    let mut out = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => loop {
+                match chars.next() {
+                    Some('\'') => break,
+                    Some(c) => out.push(c),
+                    None => return Err("unterminated single quote".into()),
+                }
+            },
+            '"' => loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => match chars.next() {
+                        Some(c @ ('$' | '`' | '"' | '\\')) => out.push(c),
+                        Some('\n') => {}
+                        Some(c) => {
+                            out.push('\\');
+                            out.push(c);
+                        }
+                        None => return Err("unterminated double quote".into()),
+                    },
+                    Some(c) => out.push(c),
+                    None => return Err("unterminated double quote".into()),
+                }
+            },
+            '\\' => match chars.next() {
+                Some('\n') => {}
+                Some(c) => out.push(c),
+                None => return Err("trailing backslash".into()),
+            },
+            c => out.push(c),
+        }
+    }
+    Ok(out)
+}
+
+impl Transform {
+    pub fn apply(&self, text: &str) -> Result<String, String> {
+        Ok(match self {
+            Transform::Case(case) => change_case(text, *case),
+            Transform::Trim => text.trim().to_string(),
+            Transform::TrimLines => map_lines(text, |line| line.trim().to_string()),
+            Transform::SortLines => {
+                let mut lines: Vec<_> = text.lines().collect();
+                lines.sort();
+                lines.join("\n")
+            }
+            Transform::DedupeLines => {
+                let mut seen = std::collections::HashSet::new();
+                let lines: Vec<_> = text.lines().filter(|line| seen.insert(*line)).collect();
+                lines.join("\n")
+            }
+            Transform::ReverseLines => text.lines().rev().collect::<Vec<_>>().join("\n"),
+            Transform::Reverse => text.chars().rev().collect(),
+            Transform::Escape(kind) => escape(text, *kind),
+            Transform::Unescape(Escape::Json) => unescape_json(text)?,
+            Transform::Unescape(Escape::Rust) => unescape_rust(text)?,
+            Transform::Unescape(Escape::Shell) => unescape_shell(text)?,
+            Transform::Wrap(width) => wrap(text, *width as usize),
+        })
+    }
+}
+
+/// applies the transforms in order
+pub fn apply_all(text: &str, transforms: &[Transform]) -> Result<String, String> {
+    transforms
+        .iter()
+        .try_fold(text.to_string(), |text, transform| transform.apply(&text))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_case_and_lines() {
+        assert_eq!(
+            apply_all("user id\nHTTPServer", &[Transform::Case(Case::Snake)]).unwrap(),
+            "user_id\nhttp_server"
+        );
+        assert_eq!(
+            apply_all("foo_bar", &[Transform::Case(Case::Camel)]).unwrap(),
+            "fooBar"
+        );
+        assert_eq!(
+            apply_all(
+                "  b\na\nb\n  ",
+                &[
+                    Transform::TrimLines,
+                    Transform::DedupeLines,
+                    Transform::SortLines,
+                    Transform::Trim
+                ]
+            )
+            .unwrap(),
+            "a\nb"
+        );
+        assert_eq!(
+            apply_all("the quick brown fox", &[Transform::Wrap(9)]).unwrap(),
+            "the quick\nbrown fox"
+        );
+    }
+
+    #[test]
+    fn test_escape_round_trip() {
+        let text = "it's \"quoted\"\n\tand $HOME \\ ünïcode";
+        for kind in [Escape::Json, Escape::Rust, Escape::Shell] {
+            let escaped = escape(text, kind);
+            assert_eq!(
+                Transform::Unescape(kind).apply(&escaped).unwrap(),
+                text,
+                "{:?}",
+                kind
+            );
+        }
+        assert_eq!(unescape_shell(r#"a\ b "c\$d" 'e'"#).unwrap(), "a b c$d e");
+        assert!(unescape_rust(r"\q").is_err());
+    }
+}
@@ -14,8 +14,8 @@ async getAllId(offset: number, limit: number) : Promise<string[]> {
 async getById(id: string) : Promise<Data | null> {
     return await TAURI_INVOKE("get_by_id", { id });
 },
-async getAllData(offset: number, limit: number) : Promise<AllData[]> {
-    return await TAURI_INVOKE("get_all_data", { offset, limit });
+async getAllData(offset: number, limit: number, tag: string | null) : Promise<AllData[]> {
+    return await TAURI_INVOKE("get_all_data", { offset, limit, tag });
 },
 async deleteById(id: string) : Promise<Result<null, null>> {
     try {
@@ -25,7 +25,10 @@ async deleteById(id: string) : Promise<Result<null, null>> {
     else return { status: "error", error: e  as any };
 }
 },
-async updateDataById(id: string, newData: Data) : Promise<Result<null, null>> {
+/**
+ * returns the new id of the entry, ids follow the value
+ */
+async updateDataById(id: string, newData: Data) : Promise<Result<string, null>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("update_data_by_id", { id, newData }) };
 } catch (e) {
@@ -39,21 +42,325 @@ async getPoolClipboardState() : Promise<boolean> {
 async setPoolClipboardState(value: boolean) : Promise<void> {
     await TAURI_INVOKE("set_pool_clipboard_state", { value });
 },
-async fuzzySearch(query: string) : Promise<Result<Data[], string>> {
+/**
+ * `query` supports filters, see `query::parse`, the remaining words are fuzzy matched
+ */
+async fuzzySearch(query: string, offset: number, limit: number) : Promise<Result<SearchResult[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("fuzzy_search", { query }) };
+    return { status: "ok", data: await TAURI_INVOKE("fuzzy_search", { query, offset, limit }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async formatJson(id: string) : Promise<Result<null, null>> {
+/**
+ * pretty prints a json entry, returns its new id
+ */
+async formatJson(id: string) : Promise<Result<string, null>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("format_json", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
+},
+async decodeJwt(id: string, key: string | null) : Promise<Result<DecodedJwt, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("decode_jwt", { id, key }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * decodes the entry and inserts the result as a new entry, returns the new id
+ */
+async decodeEntry(id: string, codec: Codec) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("decode_entry", { id, codec }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * encodes the entry and inserts the result as a new entry, returns the new id
+ */
+async encodeEntry(id: string, codec: Codec) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("encode_entry", { id, codec }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getSettings() : Promise<Settings> {
+    return await TAURI_INVOKE("get_settings");
+},
+async setSettings(settings: Settings) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_settings", { settings }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * evaluates `path` against a json entry, with `copy` the result is also written to the clipboard
+ */
+async jsonQuery(id: string, path: string, copy: boolean) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("json_query", { id, path, copy }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async formatEntry(id: string, options: FormatOptions) : Promise<Result<string, FormatError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("format_entry", { id, options }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * minifies the entry in place, or into a new entry when `new_entry` is set. returns the id of
+ * the minified entry
+ */
+async minifyEntry(id: string, lang: ProgrammingLanguage | null, newEntry: boolean) : Promise<Result<string, FormatError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("minify_entry", { id, lang, newEntry }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * converts between json, yaml, toml and csv into a new entry, returns the new id
+ */
+async convertEntry(id: string, targetLang: ProgrammingLanguage, sourceLang: ProgrammingLanguage | null) : Promise<Result<string, FormatError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("convert_entry", { id, targetLang, sourceLang }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * applies the transforms in order, in place or into a new entry when `new_entry` is set.
+ * the result is classified again, returns the id of the transformed entry
+ */
+async transformEntry(id: string, transforms: Transform[], newEntry: boolean) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("transform_entry", { id, transforms, newEntry }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * previous values of an entry, oldest first
+ */
+async listRevisions(id: string) : Promise<Data[]> {
+    return await TAURI_INVOKE("list_revisions", { id });
+},
+async revertRevision(id: string, index: number) : Promise<Result<string, null>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("revert_revision", { id, index }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async diffEntries(idA: string, idB: string, mode: DiffMode) : Promise<Result<Diff, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("diff_entries", { idA, idB, mode }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * joins the entries in the given order into a new entry, returns the new id
+ */
+async joinEntries(ids: string[], separator: string, mode: JoinMode) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("join_entries", { ids, separator, mode }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * splits the entry into new entries, each classified on its own. pieces are trimmed and empty
+ * ones skipped, returns the new ids in the order of the pieces. fails when there are more
+ * pieces than the history can hold
+ */
+async splitEntry(id: string, splitter: Splitter) : Promise<Result<string[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("split_entry", { id, splitter }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * finds emails, phone numbers, urls, ips, uuids and jwts in the entry, with `insert` each match
+ * is also added to the history
+ */
+async extractFromEntry(id: string, kinds: ExtractKind[], insert: boolean) : Promise<Result<Extracted[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("extract_from_entry", { id, kinds, insert }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listDetectors() : Promise<DetectorInfo[]> {
+    return await TAURI_INVOKE("list_detectors");
+},
+/**
+ * substring or whole word search answered from the search index, most recent first
+ */
+async search(query: string, mode: SearchMode, offset: number, limit: number) : Promise<AllData[]> {
+    return await TAURI_INVOKE("search", { query, mode, offset, limit });
+},
+async setPinned(id: string, pinned: boolean) : Promise<Result<null, null>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_pinned", { id, pinned }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * entries matching `pattern`, most recent first. stops early with `timed_out` set when the
+ * scan runs past its deadline
+ */
+async regexSearch(pattern: string, caseInsensitive: boolean, offset: number, limit: number) : Promise<Result<RegexSearch, RegexError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("regex_search", { pattern, caseInsensitive, offset, limit }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * like `fuzzy_search` but returns straight away with the search generation and sends the
+ * ranked results over `on_event` in batches. starting a new search cancels the previous one,
+ * which then stops without sending `Done`
+ */
+async streamSearch(query: string, batchSize: number, onEvent: TAURI_CHANNEL<SearchEvent>) : Promise<Result<number, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("stream_search", { query, batchSize, onEvent }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * cancels the running streaming search, if any
+ */
+async cancelSearch() : Promise<void> {
+    await TAURI_INVOKE("cancel_search");
+},
+/**
+ * entries about the same thing as `query` even when the words differ slightly, computed from
+ * local hashed n-gram vectors
+ */
+async similarSearch(query: string, limit: number) : Promise<Result<SimilarResult[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("similar_search", { query, limit }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async tagEntry(id: string, tag: string) : Promise<Result<null, null>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("tag_entry", { id, tag }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async untagEntry(id: string, tag: string) : Promise<Result<null, null>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("untag_entry", { id, tag }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listCollections() : Promise<Collection[]> {
+    return await TAURI_INVOKE("list_collections");
+},
+async listSnippets(folder: string | null) : Promise<Snippet[]> {
+    return await TAURI_INVOKE("list_snippets", { folder });
+},
+async listSnippetFolders() : Promise<string[]> {
+    return await TAURI_INVOKE("list_snippet_folders");
+},
+async createSnippet(name: string, text: string, abbreviation: string | null, folder: string | null) : Promise<Result<Snippet, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_snippet", { name, text, abbreviation, folder }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * saves a history entry as a snippet, the entry itself stays in the history
+ */
+async snippetFromEntry(id: string, name: string, abbreviation: string | null, folder: string | null) : Promise<Result<Snippet, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("snippet_from_entry", { id, name, abbreviation, folder }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateSnippet(snippet: Snippet) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_snippet", { snippet }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteSnippet(id: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_snippet", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * expands the snippet placeholders with `inputs` answering the labels from `snippet_inputs`
+ * and writes the result to the clipboard, which is also returned. `key` is a snippet id or
+ * abbreviation
+ */
+async copySnippet(key: string, inputs: Partial<{ [key in string]: string }>) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("copy_snippet", { key, inputs }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * labels the user has to fill in before `copy_snippet` can expand the snippet, `key` is a
+ * snippet id or abbreviation
+ */
+async snippetInputs(key: string) : Promise<Result<string[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("snippet_inputs", { key }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
 }
 }
 
@@ -68,8 +375,196 @@ async formatJson(id: string) : Promise<Result<null, null>> {
 /** user-defined types **/
 
 export type AllData = { id: string; data: Data }
-export type Data = { tag: "Email"; content: string } | { tag: "PhoneNumber"; content: string } | { tag: "Code"; content: { data: string; lang: ProgrammingLanguage } }
-export type ProgrammingLanguage = "rust" | "cpp" | "c" | "javascript" | "typescript" | "python" | "go" | "java" | "kotlin" | "swift" | "ruby" | "php" | "csharp" | "html" | "css" | "sql" | "json" | "markdown" | "string"
+export type Case = "camel" | "pascal" | "snake" | "kebab" | "constant" | "title" | "upper" | "lower"
+export type ChangeKind = "equal" | "insert" | "delete" | 
+/**
+ * only used by json diffs, for a value that changed in place
+ */
+"replace"
+export type Codec = "base64" | "base64url" | "hex" | "url"
+/**
+ * a tag and how many entries carry it
+ */
+export type Collection = { name: string; count: number }
+export type Data = { tag: "Email"; content: string } | { tag: "PhoneNumber"; content: { data: string; 
+/**
+ * normalized E.164 form, eg `+14155552671`
+ */
+e164: string } } | { tag: "Url"; content: string } | { tag: "Ip"; content: string } | { tag: "Uuid"; content: string } | { tag: "Jwt"; content: string } | { tag: "Encoded"; content: { data: string; codec: Codec } } | 
+/**
+ * json parsed once at ingest, `data` keeps the original text
+ */
+{ tag: "JsonDict"; content: { data: string; value: unknown } } | { tag: "Code"; content: { data: string; lang: ProgrammingLanguage } }
+export type DecodedJwt = { 
+/**
+ * token with the signature masked out
+ */
+token: string; 
+/**
+ * header and claims are returned as pretty printed json strings
+ */
+header: string; claims: string; expiry: JwtExpiry; signature: JwtSignature }
+export type DetectorInfo = { kind: DetectorKind; priority: number; enabled: boolean }
+export type DetectorKind = "ip" | "phonenumber" | "email" | "url" | "uuid" | "jwt" | "encoded" | "json"
+export type Diff = { tag: "Text"; content: { 
+/**
+ * unified diff for line mode, `[-old-]{+new+}` markup for word mode
+ */
+unified: string; changes: TextChange[] } } | { tag: "Json"; content: JsonChange[] }
+export type DiffMode = "line" | "word" | 
+/**
+ * structural diff of two json values, key order is ignored
+ */
+"json"
+export type Escape = "json" | "rust" | "shell"
+export type ExtractKind = "email" | "phonenumber" | "url" | "ip" | "uuid" | "jwt"
+export type Extracted = { kind: ExtractKind; value: string; 
+/**
+ * id of the inserted entry when extraction was asked to insert
+ */
+id: string | null }
+export type FormatError = { kind: "NotFound" } | { kind: "Unsupported"; content: ProgrammingLanguage } | { kind: "Parse"; content: { lang: ProgrammingLanguage; message: string } } | 
+/**
+ * formatting would drop the comments of the entry
+ */
+{ kind: "HasComments"; content: ProgrammingLanguage }
+export type FormatOptions = { indent_width: number; use_tabs: boolean; 
+/**
+ * overrides the language of the entry, eg for yaml that was stored as a plain string
+ */
+lang: ProgrammingLanguage | null }
+export type JoinMode = 
+/**
+ * joins the text of the entries with the separator
+ */
+"concat" | 
+/**
+ * deep merges json objects or concatenates json arrays, the separator is ignored
+ */
+"merge"
+export type JsonChange = { 
+/**
+ * JSONPath of the changed value, eg `$.spec.ports[0]`
+ */
+path: string; kind: ChangeKind; old: string | null; new: string | null }
+export type JwtExpiry = { status: "NoExpiry" } | { status: "Valid"; at: number } | { status: "Expired"; at: number } | { status: "NotYetValid"; at: number }
+export type JwtSignature = { status: "Unverified" } | { status: "Valid" } | { status: "Invalid" } | { status: "Unsupported"; reason: string }
+export type ProgrammingLanguage = "rust" | "cpp" | "c" | "javascript" | "typescript" | "python" | "go" | "java" | "kotlin" | "swift" | "ruby" | "php" | "csharp" | "html" | "css" | "sql" | "json" | "yaml" | "toml" | "xml" | "csv" | "markdown" | "string"
+/**
+ * weights blended into the search rank, each signal is scaled to 0..1 before weighting
+ */
+export type Ranking = { 
+/**
+ * fuzzy match score relative to the best match
+ */
+fuzzy: number; 
+/**
+ * how recently the entry was last copied
+ */
+recency: number; 
+/**
+ * how often the entry was copied relative to the most copied match
+ */
+frequency: number; pinned: number; 
+/**
+ * hours after which the recency signal has halved
+ */
+recency_half_life_hours: number }
+export type RegexError = { kind: "TooLong"; content: { max: number } } | { kind: "Compile"; content: string }
+export type RegexMatch = { id: string; spans: Span[]; data: Data }
+export type RegexSearch = { matches: RegexMatch[]; 
+/**
+ * the deadline was hit before every entry was searched
+ */
+timed_out: boolean }
+export type SearchEvent = 
+/**
+ * next best results, in rank order
+ */
+{ tag: "Batch"; content: { generation: number; results: SearchResult[] } } | { tag: "Done"; content: { generation: number; total: number } }
+export type SearchMode = 
+/**
+ * case insensitive substring
+ */
+"substring" | 
+/**
+ * every word of the query as a whole word
+ */
+"tokens"
+export type SearchResult = { id: string; 
+/**
+ * blended rank results are ordered by, see `Ranking`
+ */
+rank: number; 
+/**
+ * raw fuzzy match score
+ */
+score: number; 
+/**
+ * char positions of the matched query characters, for highlighting
+ */
+indices: number[]; data: Data }
+/**
+ * fields missing from an older settings file keep their defaults
+ */
+export type Settings = { 
+/**
+ * ISO 3166-1 alpha-2 region used for phone numbers without a country code
+ */
+phone_region: string; 
+/**
+ * detectors skipped when classifying new entries
+ */
+disabled_detectors: DetectorKind[]; 
+/**
+ * overrides of the built in detector priorities, lower runs first
+ */
+detector_priorities: Partial<{ [key in DetectorKind]: number }>; 
+/**
+ * how search blends match quality with recency, copy count and pins
+ */
+ranking: Ranking; 
+/**
+ * keep tagged entries when the history is full
+ */
+protect_tagged: boolean; 
+/**
+ * index entries for `similar_search`
+ */
+similarity_search: boolean }
+export type SimilarResult = { id: string; 
+/**
+ * cosine similarity, 0..1
+ */
+similarity: number; data: Data }
+export type Snippet = { id: string; name: string; 
+/**
+ * short unique trigger, eg `;sig`
+ */
+abbreviation: string | null; 
+/**
+ * `/` separated path, eg `work/email`
+ */
+folder: string | null; text: string; 
+/**
+ * unix seconds
+ */
+created: number }
+/**
+ * char offsets, end exclusive
+ */
+export type Span = { start: number; end: number }
+export type Splitter = { tag: "Lines" } | { tag: "Delimiter"; content: string } | { tag: "Regex"; content: string }
+export type TextChange = { kind: ChangeKind; value: string }
+export type Transform = 
+/**
+ * applied line by line
+ */
+{ tag: "Case"; content: Case } | { tag: "Trim" } | { tag: "TrimLines" } | { tag: "SortLines" } | { tag: "DedupeLines" } | { tag: "ReverseLines" } | { tag: "Reverse" } | { tag: "Escape"; content: Escape } | { tag: "Unescape"; content: Escape } | 
+/**
+ * wraps lines at the given column, words longer than the column are kept whole
+ */
+{ tag: "Wrap"; content: number }
 
 /** tauri-specta globals **/
 
@@ -69,14 +69,52 @@ function dataSegregate(data: Data) {
 
     case "PhoneNumber": {
       {
-        return <MyEditor value={data.content} />;
+        return (
+          <div className="p-2">
+            <div>{data.content.data}</div>
+            <div className="text-sm opacity-70">{data.content.e164}</div>
+          </div>
+        );
+      }
+    }
+    case "Url": {
+      return (
+        <a className="p-2 block" href={data.content} target="_blank">
+          {data.content}
+        </a>
+      );
+    }
+    case "Ip":
+    case "Uuid": {
+      return <div className="p-2 font-mono">{data.content}</div>;
+    }
+    case "Jwt": {
+      // the token arrives masked, decode_jwt shows the claims
+      return <div className="p-2 font-mono break-all">{data.content}</div>;
+    }
+    case "Encoded": {
+      return (
+        <div className="p-2">
+          <div className="font-mono break-all">{data.content.data}</div>
+          <div className="text-sm opacity-70">{data.content.codec}</div>
+        </div>
+      );
+    }
+    case "JsonDict": {
+      {
+        return (
+          <MyEditor
+            height={"100px"}
+            width={"100%"}
+            value={data.content.data}
+            language="json"
+            options={{
+              readOnly: true,
+            }}
+          />
+        );
       }
     }
-    // case "JsonDict": {
-    //   {
-    //     return <MyEditor value={data.content as string} language="json" />;
-    //   }
-    // }
     case "Code": {
       {
         return (
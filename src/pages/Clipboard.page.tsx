@@ -1,19 +1,19 @@
 import React, { useState, useEffect } from "react";
 
-import { AllData, commands, Data, Result } from ".././bindings";
+import { AllData, commands, Result, SearchResult } from ".././bindings";
 import DataDisplay, { MyEditor } from "../components/MonacoDisplay";
 import PoolClipboardBool from ".././components/PoolClipboard";
 
 function Search() {
   const [query, setQuery] = useState("");
-  const [result, setResult] = useState<Result<Data[], string>>({
+  const [result, setResult] = useState<Result<SearchResult[], string>>({
     status: "ok",
     data: [],
   });
 
   const handleSearch = async () => {
     if (query.trim() !== "") {
-      let res = await commands.fuzzySearch(query);
+      let res = await commands.fuzzySearch(query, 0, 50);
       setResult((_) => res);
       console.log("log", res);
     }
@@ -21,8 +21,11 @@ function Search() {
   let renderResult = () => {
     switch (result.status) {
       case "ok":
-        return result.data.map((data) => <DataDisplay data={data} />);
+        return result.data.map((result) => (
+          <DataDisplay key={result.id} data={result.data} />
+        ));
       case "error":
+        return <div>{result.error}</div>;
     }
   };
   return (
@@ -47,7 +50,7 @@ function Clipboard() {
   useEffect(() => {
     async function fetchIds() {
       try {
-        const data = await commands.getAllData(0, 10);
+        const data = await commands.getAllData(0, 10, null);
         setAllData(data);
       } catch (error) {
         console.error("Error fetching IDs:", error);